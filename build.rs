@@ -0,0 +1,47 @@
+//! Generates the registry of day solutions that the `all` and `time` commands run in-process.
+use std::{env, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    // NOTE: solution files are named `YYYY_DD.rs`.
+    let mut puzzles: Vec<String> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    let id = name.strip_suffix(".rs")?;
+                    let (year, day) = id.split_once('_')?;
+                    let is_puzzle = year.len() == 4
+                        && day.len() == 2
+                        && year.chars().chain(day.chars()).all(|c| c.is_ascii_digit());
+                    is_puzzle.then(|| id.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    puzzles.sort_unstable();
+
+    let mut registry = String::from("// @generated by build.rs, do not edit.\n\n");
+
+    for id in &puzzles {
+        let path = bin_dir.join(format!("{id}.rs"));
+        registry.push_str(&format!(
+            "#[allow(dead_code, unused_imports)]\n#[path = {path:?}]\nmod day_{id};\n\n"
+        ));
+    }
+
+    registry.push_str("pub static SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[\n");
+    for id in &puzzles {
+        registry.push_str(&format!("    day_{id}::SOLUTION,\n"));
+    }
+    registry.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), registry).unwrap();
+}
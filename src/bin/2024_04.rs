@@ -36,10 +36,10 @@ pub fn part_one(input: &str) -> Option<u32> {
                 {
                     // Check remaining characters inline
                     let mut matches = true;
-                    for i in 1..4 {
+                    for (i, &target) in TARGET.iter().enumerate().skip(1) {
                         let r = (row as i32 + dy * i as i32) as usize;
                         let c = (col as i32 + dx * i as i32) as usize;
-                        if grid[r][c] != TARGET[i] {
+                        if grid[r][c] != target {
                             matches = false;
                             break;
                         }
@@ -103,13 +103,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(18));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(9));
     }
 }
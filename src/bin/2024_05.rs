@@ -2,9 +2,9 @@ use std::collections::HashMap;
 
 advent_of_code::solution!(5);
 
-fn parse_rules_and_input(
-    input: &str,
-) -> Option<(std::collections::HashMap<(u32, u32), bool>, Vec<Vec<u32>>)> {
+type Rules = HashMap<(u32, u32), bool>;
+
+fn parse_rules_and_input(input: &str) -> Option<(Rules, Vec<Vec<u32>>)> {
     let mut sections = input.split("\n\n");
     let rules_section = sections.next()?;
     let updates_section = sections.next()?;
@@ -145,13 +145,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(143));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(123));
     }
 }
@@ -14,7 +14,7 @@ fn check(target: u64, operands: &[u64], current_index: usize, part1: bool) -> bo
     }
 
     // Try multiplication if target is divisible
-    if target % current_operand == 0
+    if target.is_multiple_of(current_operand)
         && check(target / current_operand, operands, current_index - 1, part1)
     {
         return true;
@@ -34,10 +34,11 @@ fn check(target: u64, operands: &[u64], current_index: usize, part1: bool) -> bo
             concat_value /= 10;
         }
 
-        if concat_value == 0 && target_value > 0 {
-            if check(target_value, operands, current_index - 1, part1) {
-                return true;
-            }
+        if concat_value == 0
+            && target_value > 0
+            && check(target_value, operands, current_index - 1, part1)
+        {
+            return true;
         }
     }
 
@@ -79,13 +80,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(3749));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(11387));
     }
 }
@@ -5,7 +5,7 @@ pub fn parse(input: &str) -> Vec<i32> {
     input.bytes().enumerate().for_each(|(i, b)| {
         let len = (b - b'0') as usize;
         let value = if i & 1 == 0 { i as i32 >> 1 } else { -1 };
-        result.extend(std::iter::repeat(value).take(len));
+        result.extend(std::iter::repeat_n(value, len));
     });
     result
 }
@@ -122,13 +122,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(1928));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2858));
     }
 }
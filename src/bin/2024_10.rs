@@ -8,7 +8,6 @@ pub fn part_one(input: &str) -> Option<u32> {
     let cols = grid[0].len();
 
     let total_score: u32 = (0..rows)
-        .into_iter()
         .map(|i| {
             (0..cols)
                 .filter(|&j| grid[i][j] == 0)
@@ -81,7 +80,6 @@ pub fn part_two(input: &str) -> Option<u32> {
     let cols = grid[0].len();
 
     let total_rating: u32 = (0..rows)
-        .into_iter()
         .map(|i| {
             (0..cols)
                 .filter(|&j| grid[i][j] == 0)
@@ -103,6 +101,7 @@ fn count_distinct_paths(
     let mut path_count = 0;
     let mut current_path = Vec::with_capacity(rows * cols);
 
+    #[allow(clippy::too_many_arguments)]
     fn dfs(
         i: usize,
         j: usize,
@@ -193,13 +192,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(36));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(81));
     }
 }
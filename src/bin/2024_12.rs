@@ -158,6 +158,7 @@ fn collect_region(
 }
 
 #[inline]
+#[allow(clippy::collapsible_if)]
 fn calculate_discount(region: &HashSet<(usize, usize)>, limit: (usize, usize)) -> u32 {
     let mut total = 0;
     for &(y, x) in region {
@@ -214,13 +215,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(1930));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(1206));
     }
 }
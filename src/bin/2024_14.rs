@@ -1,5 +1,12 @@
 advent_of_code::solution!(14);
 
+advent_of_code::params! {
+    /// Width of the space the robots move in.
+    width: i32 = 101,
+    /// Height of the space the robots move in.
+    height: i32 = 103,
+}
+
 type Point = (i32, i32);
 type Robot = (Point, Point); // (position, velocity)
 
@@ -17,7 +24,7 @@ fn parse_robot(line: &str) -> Robot {
     )
 }
 
-fn simulate_step(robots: &mut Vec<Robot>, width: i32, height: i32) {
+fn simulate_step(robots: &mut [Robot], width: i32, height: i32) {
     // Process robots in chunks for better cache utilization
     for chunk in robots.chunks_mut(32) {
         for robot in chunk {
@@ -68,8 +75,7 @@ fn count_robots_in_quadrants(
 
 pub fn part_one(input: &str) -> Option<u32> {
     let mut robots: Vec<Robot> = input.lines().map(parse_robot).collect();
-    let width = 101;
-    let height = 103;
+    let Params { width, height } = Params::get();
 
     // Simulate for 100 steps
     for _ in 0..100 {
@@ -82,8 +88,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 pub fn part_two(input: &str) -> Option<u32> {
     let mut robots: Vec<Robot> = input.lines().map(parse_robot).collect();
-    let width = 101;
-    let height = 103;
+    let Params { width, height } = Params::get();
 
     let mut positions = vec![0u8; (width * height) as usize];
 
@@ -125,18 +130,5 @@ pub fn part_two(input: &str) -> Option<u32> {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(21));
-    }
-
-    #[test]
-    #[ignore]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
+    advent_of_code::examples!(a);
 }
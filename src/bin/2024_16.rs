@@ -204,11 +204,9 @@ pub fn part_two(input: &str) -> Option<u32> {
     }
 
     let mut visited = HashSet::new();
-    for path in paths {
-        if let Some(path) = path {
-            for pos in path {
-                visited.insert(pos);
-            }
+    for path in paths.into_iter().flatten() {
+        for pos in path {
+            visited.insert(pos);
         }
     }
 
@@ -221,13 +219,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(11048));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(64));
     }
 }
@@ -1,5 +1,36 @@
 advent_of_code::solution!(17);
 
+use std::error::Error;
+use std::fmt::Display;
+use std::num::ParseIntError;
+
+/// An error in the puzzle input.
+#[derive(Debug)]
+pub enum ParseError {
+    MissingRegister,
+    MissingProgram,
+    InvalidNumber(String, ParseIntError),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::MissingRegister => f.write_str("input is missing `Register A: <value>`"),
+            ParseError::MissingProgram => f.write_str("input is missing `Program: <values>`"),
+            ParseError::InvalidNumber(line, _) => write!(f, "could not parse line `{line}`"),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::InvalidNumber(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug)]
 struct Computer {
     registers: [i64; 3], // A, B, C
@@ -81,60 +112,49 @@ impl Computer {
     }
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    // Pre-allocate vector with capacity
-    let mut numbers = Vec::with_capacity(16);
-
-    for line in input.lines().filter(|line| !line.is_empty()) {
-        if line.starts_with("Register A:") {
-            numbers.push(line.split(": ").nth(1).unwrap().parse().unwrap());
-        } else if line.starts_with("Program:") {
-            numbers.extend(
-                line.split(": ")
-                    .nth(1)
-                    .unwrap()
-                    .split(',')
-                    .map(|n| n.trim().parse::<i64>().unwrap()),
-            );
+/// Parses the initial value of register A and the program.
+fn parse(input: &str) -> Result<(i64, Vec<i64>), ParseError> {
+    let mut register_a = None;
+    let mut program = None;
+
+    for line in input.lines().map(str::trim) {
+        if let Some(value) = line.strip_prefix("Register A:") {
+            let value = value
+                .trim()
+                .parse()
+                .map_err(|e| ParseError::InvalidNumber(line.to_string(), e))?;
+            register_a = Some(value);
+        } else if let Some(values) = line.strip_prefix("Program:") {
+            let values = values
+                .split(',')
+                .map(|n| n.trim().parse::<i64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| ParseError::InvalidNumber(line.to_string(), e))?;
+            program = Some(values);
         }
     }
-    let initial_a = numbers[0];
-    let program = numbers[1..].to_vec();
+
+    Ok((
+        register_a.ok_or(ParseError::MissingRegister)?,
+        program.ok_or(ParseError::MissingProgram)?,
+    ))
+}
+
+pub fn part_one(input: &str) -> Result<String, ParseError> {
+    let (initial_a, program) = parse(input)?;
 
     let mut computer = Computer::new(program, initial_a, 0, 0);
     let output = computer.run();
 
-    Some(
-        output
-            .iter()
-            .map(|n| n.to_string())
-            .collect::<Vec<String>>()
-            .join(","),
-    )
+    Ok(output
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<String>>()
+        .join(","))
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    // Parse input similar to part_one
-    let numbers: Vec<i64> = input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .flat_map(|line| {
-            if line.starts_with("Register A:") {
-                vec![line.split(": ").nth(1).unwrap().parse().unwrap()]
-            } else if line.starts_with("Program:") {
-                line.split(": ")
-                    .nth(1)
-                    .unwrap()
-                    .split(',')
-                    .map(|n| n.trim().parse().unwrap())
-                    .collect()
-            } else {
-                vec![]
-            }
-        })
-        .collect();
-
-    let program = numbers[1..].to_vec();
+pub fn part_two(input: &str) -> Result<String, ParseError> {
+    let (_, program) = parse(input)?;
 
     // Initialize factors array to track digits in different positions
     let mut factors = vec![0; program.len()];
@@ -150,7 +170,7 @@ pub fn part_two(input: &str) -> Option<String> {
         let output = computer.run();
 
         if output == program {
-            return Some(init_a.to_string());
+            return Ok(init_a.to_string());
         }
 
         // Update factors based on mismatch position
@@ -170,16 +190,28 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
+            "examples", PUZZLE, 1,
         ));
-        assert_eq!(result, Some("4,6,3,5,6,3,5,2,1,0".to_string()));
+        assert_eq!(result.unwrap(), "4,6,3,5,6,3,5,2,1,0");
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples", PUZZLE, 2,
+        ));
+        assert_eq!(result.unwrap(), "117440");
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        assert!(matches!(
+            part_one("Register A: x\n\nProgram: 0,1"),
+            Err(ParseError::InvalidNumber(..))
+        ));
+        assert!(matches!(
+            part_two("Register A: 1"),
+            Err(ParseError::MissingProgram)
         ));
-        assert_eq!(result, Some("117440".to_string()));
     }
 }
@@ -2,15 +2,26 @@ advent_of_code::solution!(18);
 
 use std::collections::{HashSet, VecDeque};
 
-const GRID_SIZE: usize = 71;
+advent_of_code::params! {
+    /// Width and height of the memory space.
+    grid_size: usize = 71,
+    /// Number of bytes that have fallen when part one is solved.
+    bytes: usize = 1024,
+}
+
 const DIRS: [(i32, i32); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
 
-fn can_reach_target(blocked: &HashSet<(i32, i32)>, start: (i32, i32), target: (i32, i32)) -> bool {
-    let mut queue = VecDeque::with_capacity(GRID_SIZE * GRID_SIZE);
-    let mut visited = vec![false; GRID_SIZE * GRID_SIZE];
+fn can_reach_target(
+    blocked: &HashSet<(i32, i32)>,
+    start: (i32, i32),
+    target: (i32, i32),
+    grid_size: usize,
+) -> bool {
+    let mut queue = VecDeque::with_capacity(grid_size * grid_size);
+    let mut visited = vec![false; grid_size * grid_size];
 
     queue.push_back(start);
-    visited[(start.1 as usize) * GRID_SIZE + (start.0 as usize)] = true;
+    visited[(start.1 as usize) * grid_size + (start.0 as usize)] = true;
 
     while let Some((x, y)) = queue.pop_front() {
         if (x, y) == target {
@@ -21,8 +32,8 @@ fn can_reach_target(blocked: &HashSet<(i32, i32)>, start: (i32, i32), target: (i
             let new_x = x + dx;
             let new_y = y + dy;
 
-            if new_x >= 0 && new_x < GRID_SIZE as i32 && new_y >= 0 && new_y < GRID_SIZE as i32 {
-                let idx = (new_y as usize) * GRID_SIZE + (new_x as usize);
+            if new_x >= 0 && new_x < grid_size as i32 && new_y >= 0 && new_y < grid_size as i32 {
+                let idx = (new_y as usize) * grid_size + (new_x as usize);
                 if !visited[idx] && !blocked.contains(&(new_x, new_y)) {
                     visited[idx] = true;
                     queue.push_back((new_x, new_y));
@@ -34,10 +45,13 @@ fn can_reach_target(blocked: &HashSet<(i32, i32)>, start: (i32, i32), target: (i
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
+    let Params { grid_size, bytes } = Params::get();
+    let max = grid_size as i32 - 1;
+
     // Parse coordinates into a set of blocked positions
     let blocked: HashSet<(i32, i32)> = input
         .lines()
-        .take(1024) // Only consider first 1024 bytes
+        .take(bytes) // Only consider the first bytes that have fallen
         .map(|line| {
             let mut parts = line.split(',');
             let x = parts.next().unwrap().parse().unwrap();
@@ -53,8 +67,8 @@ pub fn part_one(input: &str) -> Option<u32> {
     visited.insert((0, 0));
 
     while let Some(((x, y), steps)) = queue.pop_front() {
-        // Check if we reached the target (70,70)
-        if x == 70 && y == 70 {
+        // Check if we reached the target in the bottom right corner
+        if x == max && y == max {
             return Some(steps);
         }
 
@@ -63,10 +77,8 @@ pub fn part_one(input: &str) -> Option<u32> {
             let new_y = y + dy;
 
             // Check bounds and if position is not blocked
-            if new_x >= 0
-                && new_x <= 70
-                && new_y >= 0
-                && new_y <= 70
+            if (0..=max).contains(&new_x)
+                && (0..=max).contains(&new_y)
                 && !blocked.contains(&(new_x, new_y))
                 && !visited.contains(&(new_x, new_y))
             {
@@ -80,6 +92,9 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<String> {
+    let Params { grid_size, .. } = Params::get();
+    let target = (grid_size as i32 - 1, grid_size as i32 - 1);
+
     let coordinates: Vec<_> = input
         .lines()
         .map(|line| {
@@ -93,7 +108,7 @@ pub fn part_two(input: &str) -> Option<String> {
     let mut blocked = HashSet::with_capacity(coordinates.len());
 
     // Check if initial path exists
-    if !can_reach_target(&blocked, (0, 0), (70, 70)) {
+    if !can_reach_target(&blocked, (0, 0), target, grid_size) {
         return Some("0,0".to_string());
     }
 
@@ -101,7 +116,7 @@ pub fn part_two(input: &str) -> Option<String> {
         blocked.insert(coord);
 
         // Early exit if this coordinate blocks the only possible path
-        if !can_reach_target(&blocked, (0, 0), (70, 70)) {
+        if !can_reach_target(&blocked, (0, 0), target, grid_size) {
             return Some(format!("{},{}", coord.0, coord.1));
         }
     }
@@ -112,19 +127,14 @@ pub fn part_two(input: &str) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::{examples::read_example, params::with_overrides};
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        // Example only does 12 bytes of input but we test it with 1024 bytes aswell
-        assert_eq!(result, Some(146));
-    }
+    advent_of_code::examples!(a);
 
     #[test]
-    // This test is ignored because it would need a different implementation
-    #[ignore]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("6,1".to_string()));
+    fn test_part_one_after_blocking_byte() {
+        let input = read_example(PUZZLE, "a");
+        let result = with_overrides(&[("grid_size", "7"), ("bytes", "21")], || part_one(&input));
+        assert_eq!(result, None);
     }
 }
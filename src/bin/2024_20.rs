@@ -2,6 +2,11 @@ use std::collections::VecDeque;
 
 advent_of_code::solution!(20);
 
+advent_of_code::params! {
+    /// Minimum number of picoseconds a cheat has to save to be counted.
+    min_saving: u32 = 100,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
 struct Point {
     x: i32,
@@ -44,7 +49,7 @@ fn parse_map(input: &str) -> (Vec<Vec<char>>, Point, Point) {
     (map, start, end)
 }
 
-fn calculate_distances(map: &Vec<Vec<char>>, start: Point) -> Vec<Vec<u32>> {
+fn calculate_distances(map: &[Vec<char>], start: Point) -> Vec<Vec<u32>> {
     let rows = map.len();
     let cols = map[0].len();
     let mut distances = vec![vec![u32::MAX; cols]; rows];
@@ -93,6 +98,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     let end_distances = calculate_distances(&map, end);
 
     let orig_distance = end_distances[start.y as usize][start.x as usize];
+    let Params { min_saving } = Params::get();
     let mut count = 0;
 
     for r in 0..map.len() {
@@ -123,7 +129,7 @@ pub fn part_one(input: &str) -> Option<u32> {
                         + end_distances[end_point.y as usize][end_point.x as usize]
                         + 2;
 
-                    if (new_distance + 100) <= orig_distance {
+                    if (new_distance + min_saving) <= orig_distance {
                         count += 1;
                     }
                 }
@@ -140,6 +146,7 @@ pub fn part_two(input: &str) -> Option<u32> {
     let end_distances = calculate_distances(&map, end);
 
     let orig_distance = end_distances[start.y as usize][start.x as usize];
+    let Params { min_saving } = Params::get();
     let mut count = 0;
 
     for r in 0..map.len() {
@@ -174,7 +181,7 @@ pub fn part_two(input: &str) -> Option<u32> {
                         + end_distances[end_point.y as usize][end_point.x as usize]
                         + manhattan_dist;
 
-                    if (new_distance + 100) <= orig_distance {
+                    if (new_distance + min_saving) <= orig_distance {
                         count += 1;
                     }
                 }
@@ -187,17 +194,5 @@ pub fn part_two(input: &str) -> Option<u32> {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(0));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(0));
-    }
+    advent_of_code::examples!(a, b);
 }
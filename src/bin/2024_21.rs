@@ -90,14 +90,12 @@ fn cost_step(
                 let mut new_presses = presses.clone();
                 new_presses.push(b'v');
                 q.push_back((p, new_presses));
-                p.y -= 1;
             }
             Ordering::Greater => {
                 p.y -= 1;
                 let mut new_presses = presses.clone();
                 new_presses.push(b'^');
                 q.push_back((p, new_presses));
-                p.y += 1;
             }
             Ordering::Equal => {}
         }
@@ -187,14 +185,14 @@ mod tests {
     #[test]
     #[ignore]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(126384));
     }
 
     #[test]
     #[ignore]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 }
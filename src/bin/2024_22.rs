@@ -49,9 +49,9 @@ pub fn part_two(input: &str) -> Option<u32> {
 
         // Initialize the rolling window
         window[0] = (secret % 10) as i32;
-        for i in 1..5 {
+        for slot in window.iter_mut().skip(1) {
             secret = next_secret(secret);
-            window[i] = (secret % 10) as i32;
+            *slot = (secret % 10) as i32;
         }
 
         for _ in 0..2000 {
@@ -75,8 +75,8 @@ pub fn part_two(input: &str) -> Option<u32> {
     }
 
     pattern_values
-        .into_iter()
-        .map(|(_, values)| values.into_iter().sum::<i32>() as u32)
+        .into_values()
+        .map(|values| values.into_iter().sum::<i32>() as u32)
         .max()
 }
 
@@ -87,7 +87,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
+            "examples", PUZZLE, 1,
         ));
         assert_eq!(result, Some(37327623));
     }
@@ -95,7 +95,7 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples", PUZZLE, 2,
         ));
         assert_eq!(result, Some(23));
     }
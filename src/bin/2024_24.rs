@@ -5,6 +5,7 @@ advent_of_code::solution!(24);
 
 type Gate<'a> = (&'a str, &'a str, &'a str);
 type GateMap<'a> = HashMap<&'a str, Gate<'a>>;
+type Categories<'a> = HashMap<&'a str, Vec<(&'a str, Gate<'a>)>>;
 
 #[derive(Debug)]
 enum GateOperation {
@@ -45,13 +46,12 @@ fn calculate_signal_value(signals: &HashMap<&str, bool>, prefix: char) -> usize
     signals
         .iter()
         .filter(|(&name, _)| name.starts_with(prefix))
-        .filter_map(|(name, &value)| {
-            value.then(|| {
-                name[1..]
-                    .parse::<usize>()
-                    .map(|n| 1 << n)
-                    .unwrap_or_default()
-            })
+        .filter(|(_, &value)| value)
+        .map(|(name, _)| {
+            name[1..]
+                .parse::<usize>()
+                .map(|n| 1 << n)
+                .unwrap_or_default()
         })
         .sum()
 }
@@ -70,7 +70,7 @@ fn parse_input(input: &str) -> Option<(HashMap<&str, bool>, &str)> {
     Some((initial_states, circuit))
 }
 
-fn parse_gates(circuit: &str) -> GateMap {
+fn parse_gates(circuit: &str) -> GateMap<'_> {
     circuit
         .lines()
         .filter_map(|line| {
@@ -95,7 +95,7 @@ pub fn part_two(input: &str) -> Option<String> {
     let gates = parse_gates(circuit);
 
     let mut incorrect_gates = Vec::new();
-    let mut categories: HashMap<&str, Vec<(&str, Gate)>> = HashMap::new();
+    let mut categories: Categories = HashMap::new();
 
     // Categorize gates
     for (&signal, &gate @ (in1, op, in2)) in &gates {
@@ -124,10 +124,7 @@ pub fn part_two(input: &str) -> Option<String> {
     Some(incorrect_gates.into_iter().sorted().join(","))
 }
 
-fn process_outputs<'a>(
-    categories: &HashMap<&str, Vec<(&'a str, (&'a str, &str, &'a str))>>,
-    incorrect: &mut Vec<&'a str>,
-) {
+fn process_outputs<'a>(categories: &Categories<'a>, incorrect: &mut Vec<&'a str>) {
     if let Some(outputs) = categories.get("outputs") {
         incorrect.extend(
             outputs
@@ -138,10 +135,7 @@ fn process_outputs<'a>(
     }
 }
 
-fn process_xy_sums<'a>(
-    categories: &HashMap<&str, Vec<(&'a str, (&'a str, &str, &'a str))>>,
-    incorrect: &mut Vec<&'a str>,
-) {
+fn process_xy_sums<'a>(categories: &Categories<'a>, incorrect: &mut Vec<&'a str>) {
     if let Some(xy_sums) = categories.get("xy_sum") {
         let default = Vec::new();
         let outputs = categories.get("outputs").unwrap_or(&default);
@@ -159,10 +153,7 @@ fn process_xy_sums<'a>(
     }
 }
 
-fn process_xy_carries<'a>(
-    categories: &HashMap<&str, Vec<(&'a str, (&'a str, &str, &'a str))>>,
-    incorrect: &mut Vec<&'a str>,
-) {
+fn process_xy_carries<'a>(categories: &Categories<'a>, incorrect: &mut Vec<&'a str>) {
     if let Some(xy_carries) = categories.get("xy_carry") {
         let default = Vec::new();
         let carries = categories.get("carry").unwrap_or(&default);
@@ -180,10 +171,7 @@ fn process_xy_carries<'a>(
     }
 }
 
-fn process_carries<'a>(
-    categories: &HashMap<&str, Vec<(&'a str, (&'a str, &str, &'a str))>>,
-    incorrect: &mut Vec<&'a str>,
-) {
+fn process_carries<'a>(categories: &Categories<'a>, incorrect: &mut Vec<&'a str>) {
     if let Some(carries) = categories.get("carry") {
         incorrect.extend(
             carries
@@ -194,10 +182,7 @@ fn process_carries<'a>(
     }
 }
 
-fn process_full_carries<'a>(
-    categories: &HashMap<&str, Vec<(&'a str, (&'a str, &str, &'a str))>>,
-    incorrect: &mut Vec<&'a str>,
-) {
+fn process_full_carries<'a>(categories: &Categories<'a>, incorrect: &mut Vec<&'a str>) {
     if let Some(full_carries) = categories.get("full_carry") {
         incorrect.extend(
             full_carries
@@ -214,13 +199,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2024));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(
             result,
             Some("ffh,mjb,tgd,wpb,z02,z03,z05,z06,z07,z08,z10,z11".to_string())
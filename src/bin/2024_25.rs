@@ -3,8 +3,7 @@ advent_of_code::solution!(25);
 pub fn part_one(input: &str) -> Option<u32> {
     let (mut locks, mut keys) = (Vec::with_capacity(3500), Vec::with_capacity(3500));
 
-    let mut sections = input.split("\n\n");
-    while let Some(section) = sections.next() {
+    for section in input.split("\n\n") {
         if section.is_empty() {
             continue;
         }
@@ -54,13 +53,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(3));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 }
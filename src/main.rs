@@ -1,144 +1,426 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, profile, read, scaffold, solve, time, verify, watch,
+};
+use advent_of_code::template::{cli, legacy};
 use args::{parse, AppArguments};
 
+use advent_of_code::template::runner::Solution;
 #[cfg(feature = "today")]
 use advent_of_code::template::Day;
-#[cfg(feature = "today")]
+use advent_of_code::template::{Error, PuzzleId};
 use std::process;
 
+/// Registry of all day solutions, generated by `build.rs` from the files in `src/bin`.
+// NOTE: day tests already run as part of their own binaries, the dhat allocators of the days would clash.
+#[cfg(not(any(test, feature = "dhat-heap")))]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+#[cfg(any(test, feature = "dhat-heap"))]
+mod solutions {
+    pub static SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[];
+}
+
+// NOTE: installed here rather than by `solution!`, the days are modules of this binary and would clash.
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOC: advent_of_code::template::allocations::CountingAllocator =
+    advent_of_code::template::allocations::CountingAllocator;
+
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::cli::{self, Matches, Shell};
+    use advent_of_code::template::commands::time;
+    use advent_of_code::template::config::Config;
+    use advent_of_code::template::output::OutputFormat;
+    use advent_of_code::template::runner::DEFAULT_TIMEOUT;
+    use advent_of_code::template::{Day, Error, PuzzleId, Year};
+    use std::env;
+    use std::time::Duration;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            id: PuzzleId,
         },
         Read {
-            day: Day,
+            id: PuzzleId,
         },
         Scaffold {
-            day: Day,
+            id: PuzzleId,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
+            dry_run: bool,
+        },
+        Examples {
+            id: PuzzleId,
+            overwrite: bool,
+            dry_run: bool,
+        },
+        Profile {
+            id: PuzzleId,
+            part: Option<u8>,
         },
         Solve {
-            day: Day,
+            id: PuzzleId,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            format: Option<OutputFormat>,
+            input: Option<String>,
+            example: Option<Option<String>>,
         },
         All {
-            release: bool,
+            year: Year,
+            format: OutputFormat,
+            timeout: Duration,
+            jobs: usize,
         },
         Time {
+            year: Year,
             all: bool,
             day: Option<Day>,
             store: bool,
+            compare: Option<f64>,
+            format: OutputFormat,
+            timeout: Duration,
+        },
+        TimeHistory {
+            year: Year,
+            day: Day,
+        },
+        Verify {
+            year: Year,
+            day: Option<Day>,
+            store: bool,
+        },
+        Watch {
+            id: PuzzleId,
+            release: bool,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            year: Year,
+            template: Option<String>,
+        },
+        Completions {
+            shell: Shell,
+        },
+        Help {
+            command: Option<String>,
+        },
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+    /// Checks that the day is part of the year's event.
+    fn check_day(year: Year, day: Day) -> Result<Day, Error> {
+        if year.has_day(day) {
+            Ok(day)
+        } else {
+            Err(Error::Usage(format!(
+                "day {day} is not part of the {year} event, which runs for {} days.",
+                year.day_count()
+            )))
+        }
+    }
+
+    /// Reads the time in seconds the first run of a part may take with `--timeout`.
+    fn parse_timeout(args: &Matches) -> Result<Duration, Error> {
+        Ok(args
+            .value::<u64>("--timeout")?
+            .map_or(DEFAULT_TIMEOUT, Duration::from_secs))
+    }
+
+    /// Reads whether to compile with optimizations. The flags override `release` in `aoc.toml` either way.
+    fn parse_release(args: &Matches, config: &Config) -> Result<bool, Error> {
+        match (args.contains("--release"), args.contains("--no-release")) {
+            (true, true) => Err(Error::Usage(
+                "`--release` and `--no-release` can not be combined.".into(),
+            )),
+            (release, no_release) => Ok(release || (config.release && !no_release)),
+        }
+    }
+
+    /// Reads the day passed as the first argument of a command.
+    fn parse_day(args: &Matches, year: Year) -> Result<Day, Error> {
+        check_day(year, args.required(0)?)
+    }
+
+    /// Reads the day optionally passed as the first argument of a command.
+    fn parse_opt_day(args: &Matches, year: Year) -> Result<Option<Day>, Error> {
+        args.positional(0)?
+            .map(|day| check_day(year, day))
+            .transpose()
+    }
+
+    pub fn parse() -> Result<AppArguments, Error> {
+        let raw: Vec<String> = env::args().skip(1).collect();
+        let name = raw.first().filter(|arg| !arg.starts_with('-'));
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+        if raw.iter().any(|arg| arg == "-h" || arg == "--help") {
+            return Ok(AppArguments::Help {
+                command: name.cloned(),
+            });
+        }
+
+        let command = match name.map(|name| (name, cli::find_command(name))) {
+            Some((_, Some(command))) => command,
+            Some((name, None)) => {
+                return Err(Error::Usage(format!(
+                    "unknown command `{name}`. Run with `--help` to list the commands."
+                )))
+            }
+            None => {
+                return Err(Error::Usage(
+                    "no command specified. Run with `--help` to list the commands.".into(),
+                ))
+            }
+        };
+
+        let args = command.parse(&raw[1..])?;
+        let config = Config::load()?;
+
+        // NOTE: `--year` is accepted by every command, it defaults to `year` in `aoc.toml`, then `AOC_YEAR`.
+        let year = match args.value::<Year>("--year")? {
+            Some(year) => year,
+            None => config.default_year().ok_or_else(|| {
+                Error::Usage("no year specified. Pass `--year`, set `year` in `aoc.toml` or `AOC_YEAR` in `.cargo/config.toml`.".into())
+            })?,
+        };
+
+        let app_args = match command.name {
+            "all" => AppArguments::All {
+                year,
+                format: args.value("--format")?.unwrap_or_default(),
+                timeout: parse_timeout(&args)?,
+                jobs: args.value("--jobs")?.or(config.jobs).unwrap_or(1),
+            },
+            "time" if args.contains("--history") => AppArguments::TimeHistory {
+                year,
+                day: parse_day(&args, year)?,
             },
-            Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
+            "time" => {
+                let tolerance: Option<f64> = args.value("--tolerance")?;
+                if tolerance.is_some_and(|tolerance| !tolerance.is_finite() || tolerance < 0.0) {
+                    return Err(Error::Usage(
+                        "`--tolerance` must be a non-negative percentage.".into(),
+                    ));
+                }
+                let compare = if args.contains("--compare") || tolerance.is_some() {
+                    Some(tolerance.unwrap_or(time::DEFAULT_TOLERANCE_PERCENT))
+                } else {
+                    None
+                };
 
                 AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
+                    year,
+                    all: args.contains("--all"),
+                    day: parse_opt_day(&args, year)?,
+                    store: args.contains("--store"),
+                    compare,
+                    format: args.value("--format")?.unwrap_or_default(),
+                    timeout: parse_timeout(&args)?,
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+            "download" => AppArguments::Download {
+                id: PuzzleId::new(year, parse_day(&args, year)?),
             },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+            "read" => AppArguments::Read {
+                id: PuzzleId::new(year, parse_day(&args, year)?),
             },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+            "scaffold" => AppArguments::Scaffold {
+                id: PuzzleId::new(year, parse_day(&args, year)?),
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args.value("--template")?.or(config.template),
+                dry_run: args.contains("--dry-run"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            "examples" => AppArguments::Examples {
+                id: PuzzleId::new(year, parse_day(&args, year)?),
+                overwrite: args.contains("--overwrite"),
+                dry_run: args.contains("--dry-run"),
             },
-            #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
+            "profile" => {
+                let part: Option<u8> = args.value("--part")?;
+                if part.is_some_and(|part| !(1..=2).contains(&part)) {
+                    return Err(Error::Usage("`--part` must be 1 or 2.".into()));
+                }
+
+                AppArguments::Profile {
+                    id: PuzzleId::new(year, parse_day(&args, year)?),
+                    part,
+                }
             }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
+            "solve" => {
+                let input: Option<String> = args.value("--input")?;
+                let has_example = args.contains("--example");
+
+                // NOTE: the value after the day is the name of the example or `-` for stdin.
+                let (input, example) = match args.positional::<String>(1)? {
+                    Some(name) if has_example => (input, Some(Some(name))),
+                    Some(value) if value == "-" => (Some(value), None),
+                    Some(value) => {
+                        return Err(Error::Usage(format!("unexpected argument `{value}`.")))
+                    }
+                    None => (input, has_example.then_some(None)),
+                };
+
+                AppArguments::Solve {
+                    id: PuzzleId::new(year, parse_day(&args, year)?),
+                    release: parse_release(&args, &config)?,
+                    dhat: args.contains("--dhat"),
+                    submit: args.value("--submit")?,
+                    format: args.value("--format")?,
+                    input,
+                    example,
+                }
             }
+            "verify" => AppArguments::Verify {
+                year,
+                day: parse_opt_day(&args, year)?,
+                store: args.contains("--store"),
+            },
+            "watch" => AppArguments::Watch {
+                id: PuzzleId::new(year, parse_day(&args, year)?),
+                release: parse_release(&args, &config)?,
+            },
+            #[cfg(feature = "today")]
+            "today" => AppArguments::Today {
+                year,
+                template: config.template,
+            },
+            #[cfg(not(feature = "today"))]
+            "today" => {
+                return Err(Error::Usage(
+                    "`today` requires the `today` feature. Run it with `cargo today`.".into(),
+                ))
+            }
+            "completions" => AppArguments::Completions {
+                shell: args.required(0)?,
+            },
+            "help" => AppArguments::Help {
+                command: args.positional(0)?,
+            },
+            _ => unreachable!("commands are checked against the command-line model"),
         };
 
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
-        }
-
         Ok(app_args)
     }
 }
 
-fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
-        }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
+/// Returns the solutions of the multi-day runner. They are not compiled in with the `dhat-heap` feature.
+fn solutions() -> Result<&'static [Solution], Error> {
+    if cfg!(feature = "dhat-heap") {
+        return Err(Error::Usage(
+            "multi-day runs are unavailable with the `dhat-heap` feature. Profile a single day with `cargo solve <DAY> --dhat`.".into(),
+        ));
+    }
+
+    Ok(solutions::SOLUTIONS)
+}
+
+/// Runs a command. Failures are the only errors, warnings are printed by the commands themselves.
+fn run(args: AppArguments) -> Result<(), Error> {
+    match args {
+        AppArguments::All {
+            year,
+            format,
+            timeout,
+            jobs,
+        } => all::handle(solutions()?, year, format, timeout, jobs),
+        AppArguments::Time {
+            year,
+            day,
+            all,
+            store,
+            compare,
+            format,
+            timeout,
+        } => time::handle(
+            solutions()?,
+            year,
+            day,
+            all,
+            store,
+            compare,
+            format,
+            timeout,
+        ),
+        AppArguments::Download { id } => download::handle(id),
+        AppArguments::Read { id } => read::handle(id),
+        AppArguments::Scaffold {
+            id,
+            download,
+            overwrite,
+            template,
+            dry_run,
+        } => {
+            // NOTE: downloads first, so that the title and examples of the puzzle can be filled in.
+            if download && !dry_run {
+                download_or_warn(id);
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+            scaffold::handle(id, overwrite, template.as_deref(), dry_run)
+        }
+        AppArguments::Examples {
+            id,
+            overwrite,
+            dry_run,
+        } => examples::handle(id, overwrite, dry_run),
+        AppArguments::Profile { id, part } => profile::handle(id, part),
+        AppArguments::Solve {
+            id,
+            release,
+            dhat,
+            submit,
+            format,
+            input,
+            example,
+        } => solve::handle(id, release, dhat, submit, format, input, example),
+        AppArguments::TimeHistory { year, day } => time::handle_history(year, day),
+        AppArguments::Verify { year, day, store } => verify::handle(solutions()?, year, day, store),
+        AppArguments::Watch { id, release } => watch::handle(id, release),
+        AppArguments::Completions { shell } => {
+            print!("{}", cli::completions(shell));
+            Ok(())
+        }
+        AppArguments::Help { command } => {
+            match command.as_deref().and_then(cli::find_command) {
+                Some(command) => println!("{}", command.help()),
+                None => println!("{}", cli::help()),
             }
-        },
-    };
+            Ok(())
+        }
+        #[cfg(feature = "today")]
+        AppArguments::Today { year, template } => {
+            let day = Day::today(year).ok_or_else(|| {
+                Error::Usage(format!(
+                    "`today` command can only be run between the 1st and \
+                    the {}th of december. Please use `scaffold` with a specific day.",
+                    year.day_count()
+                ))
+            })?;
+
+            let id = PuzzleId::new(year, day);
+            download_or_warn(id);
+            scaffold::handle(id, false, template.as_deref(), false)?;
+            read::handle(id)
+        }
+    }
+}
+
+/// Downloads a puzzle before scaffolding it. Scaffolding continues without the description if that fails.
+fn download_or_warn(id: PuzzleId) {
+    if let Err(e) = download::handle(id) {
+        eprintln!("Warning: {e}");
+        eprintln!("Scaffolding without the puzzle description.");
+    }
+}
+
+fn main() {
+    if let Err(e) = parse().and_then(|args| {
+        legacy::migrate()?;
+        run(args)
+    }) {
+        eprintln!("Error: {e}");
+        process::exit(e.exit_code());
+    }
 }
@@ -0,0 +1,226 @@
+/// Counts the allocations of parts, reported next to their timings.
+///
+/// The main binary installs [`CountingAllocator`] with the `count-allocations` feature, so `cargo time` can
+/// report the allocations, allocated bytes and peak memory of each part. Counts are kept per thread and only
+/// while a part is measured on its first, untimed run. Parts running in parallel or abandoned after a timeout
+/// do not affect each other's counts, but allocations of threads spawned by a solution are not counted.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Allocations of a single run of a part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    /// Bytes allocated over the run, including memory that was freed again.
+    pub bytes: u64,
+    /// Most bytes in use at once, on top of what was in use before the run.
+    pub peak_bytes: u64,
+}
+
+/// Counters of a thread, which only count while a run is measured.
+struct Counters {
+    is_measuring: Cell<bool>,
+    allocations: Cell<u64>,
+    bytes: Cell<u64>,
+    /// Bytes in use relative to the start of the run. Negative if memory of earlier runs was freed.
+    current: Cell<i64>,
+    peak: Cell<i64>,
+}
+
+thread_local! {
+    // NOTE: a const initializer without drop glue never allocates, so the allocator can access it.
+    static COUNTERS: Counters = const { Counters::new() };
+}
+
+/// Whether the counting allocator is installed, set on its first allocation.
+static IS_INSTALLED: AtomicBool = AtomicBool::new(false);
+
+impl Counters {
+    const fn new() -> Self {
+        Self {
+            is_measuring: Cell::new(false),
+            allocations: Cell::new(0),
+            bytes: Cell::new(0),
+            current: Cell::new(0),
+            peak: Cell::new(0),
+        }
+    }
+
+    fn allocated(&self, size: usize) {
+        if !self.is_measuring.get() {
+            return;
+        }
+
+        self.allocations.set(self.allocations.get() + 1);
+        self.bytes.set(self.bytes.get() + size as u64);
+        self.current.set(self.current.get() + size as i64);
+        self.peak.set(self.peak.get().max(self.current.get()));
+    }
+
+    fn deallocated(&self, size: usize) {
+        if self.is_measuring.get() {
+            self.current.set(self.current.get() - size as i64);
+        }
+    }
+
+    fn measure<T>(&self, is_installed: bool, func: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+        if !is_installed {
+            return (func(), None);
+        }
+
+        self.allocations.set(0);
+        self.bytes.set(0);
+        self.current.set(0);
+        self.peak.set(0);
+        self.is_measuring.set(true);
+
+        let result = func();
+
+        self.is_measuring.set(false);
+        let stats = AllocStats {
+            allocations: self.allocations.get(),
+            bytes: self.bytes.get(),
+            peak_bytes: self.peak.get().max(0) as u64,
+        };
+
+        (result, Some(stats))
+    }
+}
+
+fn allocated(size: usize) {
+    if !IS_INSTALLED.load(Ordering::Relaxed) {
+        IS_INSTALLED.store(true, Ordering::Relaxed);
+    }
+    // NOTE: the counters are unavailable while the thread is torn down, its allocations are not measured then.
+    let _ = COUNTERS.try_with(|counters| counters.allocated(size));
+}
+
+fn deallocated(size: usize) {
+    let _ = COUNTERS.try_with(|counters| counters.deallocated(size));
+}
+
+/// Global allocator that counts allocations before passing them on to the system allocator.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        deallocated(layout.size());
+    }
+
+    // NOTE: a reallocation counts as a new allocation of the new size.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            deallocated(layout.size());
+            allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Runs a function and counts the allocations it makes on the current thread.
+/// Returns no stats if the counting allocator is not installed.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    let is_installed = IS_INSTALLED.load(Ordering::Relaxed);
+    COUNTERS.with(|counters| counters.measure(is_installed, func))
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, AllocStats, Counters};
+
+    #[test]
+    fn skips_measuring_without_allocator() {
+        let counters = Counters::new();
+        let (result, stats) = counters.measure(false, || 42);
+
+        assert_eq!(result, 42);
+        assert_eq!(stats, None);
+    }
+
+    #[test]
+    fn counts_allocations_of_run() {
+        let counters = Counters::new();
+        counters.allocated(1000);
+
+        let (_, stats) = counters.measure(true, || {
+            counters.allocated(64);
+            counters.deallocated(64);
+            counters.allocated(128);
+            counters.allocated(32);
+            counters.deallocated(128);
+        });
+
+        assert_eq!(
+            stats,
+            Some(AllocStats {
+                allocations: 3,
+                bytes: 224,
+                peak_bytes: 160,
+            })
+        );
+    }
+
+    #[test]
+    fn counts_only_while_measuring() {
+        let counters = Counters::new();
+
+        let (_, stats) = counters.measure(true, || counters.deallocated(512));
+        counters.allocated(64);
+
+        assert_eq!(
+            stats,
+            Some(AllocStats {
+                allocations: 0,
+                bytes: 0,
+                peak_bytes: 0,
+            })
+        );
+        assert_eq!(counters.allocations.get(), 0);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -0,0 +1,309 @@
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::output::Status;
+use crate::template::runner::PartResult;
+use crate::template::{get_year_path, Day, Year};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Represents the accepted answers for a single day.
+#[derive(Clone, Debug)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Represents the accepted answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let path = get_year_path(year);
+        fs::create_dir_all(&path)?;
+        let mut file = fs::File::create(path.join(ANSWERS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(get_year_path(year).join(ANSWERS_FILE_NAME))
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Returns the stored answer for a day and part.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let answer = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => answer.part_1.as_deref(),
+            2 => answer.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Stores an answer for a day and part, replacing a previous one.
+    pub fn set(&mut self, day: Day, part: u8, value: String) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        match part {
+            1 => self.data[index].part_1 = Some(value),
+            2 => self.data[index].part_2 = Some(value),
+            _ => {}
+        }
+    }
+
+    /// Compare the answers of a run against the stored answers.
+    pub fn verify(&self, results: &[PartResult]) -> Vec<Verification> {
+        results
+            .iter()
+            .map(|result| {
+                let expected = self.get(result.day, result.part).map(String::from);
+
+                let outcome = match (&expected, &result.answer) {
+                    _ if matches!(result.status, Status::NotImplemented | Status::MissingInput) => {
+                        Outcome::Skipped
+                    }
+                    (None, _) => Outcome::Missing,
+                    (Some(expected), Some(actual)) if expected == actual => Outcome::Pass,
+                    (Some(_), _) => Outcome::Fail,
+                };
+
+                Verification {
+                    day: result.day,
+                    part: result.part,
+                    expected,
+                    actual: result.answer.clone(),
+                    outcome,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Result of verifying a part against the stored answers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail,
+    /// No answer is stored for this part.
+    Missing,
+    /// The part could not be run.
+    Skipped,
+}
+
+/// Verification of a single day and part.
+#[derive(Clone, Debug)]
+pub struct Verification {
+    pub day: Day,
+    pub part: u8,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+    pub outcome: Outcome,
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "part_1".into(),
+            value
+                .part_1
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "part_2".into(),
+            value
+                .part_2
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Answer, Answers, Outcome};
+    use crate::{
+        day,
+        template::{output::Status, runner::PartResult, Day},
+        year,
+    };
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            data: vec![Answer {
+                day: day!(1),
+                part_1: Some("11".into()),
+                part_2: Some("31".into()),
+            }],
+        }
+    }
+
+    fn result(day: Day, part: u8, answer: Option<&str>, status: Status) -> PartResult {
+        PartResult {
+            year: year!(2024),
+            day,
+            part,
+            answer: answer.map(String::from),
+            duration: Duration::ZERO,
+            stats: None,
+            allocations: None,
+            status,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn handles_json_answers() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": "11", "part_2": null }] }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(day!(1), 1), Some("11"));
+        assert_eq!(answers.get(day!(1), 2), None);
+        assert_eq!(answers.get(day!(2), 1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "32", "part_1": null, "part_2": null }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn sets_answers() {
+        let mut answers = get_mock_answers();
+        answers.set(day!(3), 2, "7".into());
+        answers.set(day!(2), 1, "5".into());
+        answers.set(day!(1), 1, "12".into());
+
+        assert_eq!(answers.data.len(), 3);
+        assert_eq!(answers.data[1].day, day!(2));
+        assert_eq!(answers.get(day!(3), 2), Some("7"));
+        assert_eq!(answers.get(day!(1), 1), Some("12"));
+    }
+
+    #[test]
+    fn verifies_results() {
+        let answers = get_mock_answers();
+        let verifications = answers.verify(&[
+            result(day!(1), 1, Some("11"), Status::Solved),
+            result(day!(1), 2, Some("30"), Status::Solved),
+            result(day!(2), 1, Some("1"), Status::Solved),
+            result(day!(3), 1, None, Status::NotImplemented),
+        ]);
+
+        assert_eq!(verifications[0].outcome, Outcome::Pass);
+        assert_eq!(verifications[1].outcome, Outcome::Fail);
+        assert_eq!(verifications[1].expected, Some("31".into()));
+        assert_eq!(verifications[2].outcome, Outcome::Missing);
+        assert_eq!(verifications[3].outcome, Outcome::Skipped);
+    }
+
+    #[test]
+    fn fails_parts_without_answer() {
+        let answers = get_mock_answers();
+        let verifications = answers.verify(&[result(day!(1), 1, None, Status::NoAnswer)]);
+        assert_eq!(verifications[0].outcome, Outcome::Fail);
+    }
+}
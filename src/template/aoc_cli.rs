@@ -4,7 +4,10 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::PuzzleId;
+
+/// Name of the aoc-cli binary, looked up in `PATH`.
+const AOC_BINARY: &str = "aoc";
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -26,15 +29,15 @@ impl Display for AocCommandError {
 }
 
 pub fn check() -> Result<(), AocCommandError> {
-    Command::new("aoc")
+    Command::new(AOC_BINARY)
         .arg("-V")
         .output()
         .map_err(|_| AocCommandError::CommandNotFound)?;
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+pub fn read(id: PuzzleId) -> Result<Output, AocCommandError> {
+    let puzzle_path = get_puzzle_path(id);
 
     let args = build_args(
         "read",
@@ -43,15 +46,15 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
             "--puzzle-file".into(),
             puzzle_path,
         ],
-        day,
+        id,
     );
 
     call_aoc_cli(&args)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+pub fn download(id: PuzzleId) -> Result<Output, AocCommandError> {
+    let input_path = get_input_path(id);
+    let puzzle_path = get_puzzle_path(id);
 
     let args = build_args(
         "download",
@@ -62,7 +65,7 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
             "--puzzle-file".into(),
             puzzle_path.to_string(),
         ],
-        day,
+        id,
     );
 
     let output = call_aoc_cli(&args)?;
@@ -72,47 +75,58 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Submits an answer. The output of aoc-cli is captured, so the server response can be inspected.
+pub fn submit(id: PuzzleId, part: u8, result: &str) -> Result<Output, AocCommandError> {
+    submit_with(AOC_BINARY, id, part, result)
+}
+
+fn submit_with(
+    binary: &str,
+    id: PuzzleId,
+    part: u8,
+    result: &str,
+) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
+    let mut args = build_args("submit", &[], id);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
-}
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    let output = run_command(binary, &args, Stdio::piped())?;
+    eprint!("{}", String::from_utf8_lossy(&output.stdout));
+    Ok(output)
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+pub fn get_input_path(id: PuzzleId) -> String {
+    format!("data/{}/inputs/{}.txt", id.year, id.day)
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+pub fn get_puzzle_path(id: PuzzleId) -> String {
+    format!("data/{}/puzzles/{}.md", id.year, id.day)
 }
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+fn build_args(command: &str, args: &[String], id: PuzzleId) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
-    }
-
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+    cmd_args.append(&mut vec![
+        "--year".into(),
+        id.year.to_string(),
+        "--day".into(),
+        id.day.to_string(),
+        command.into(),
+    ]);
 
     cmd_args
 }
 
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
+    run_command(AOC_BINARY, args, Stdio::inherit())
+}
+
+fn run_command(binary: &str, args: &[String], stdout: Stdio) -> Result<Output, AocCommandError> {
+    let output = Command::new(binary)
         .args(args)
-        .stdout(Stdio::inherit())
+        .stdout(stdout)
         .stderr(Stdio::inherit())
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
@@ -123,3 +137,42 @@ fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
         Err(AocCommandError::BadExitStatus(output))
     }
 }
+
+#[cfg(all(feature = "test_lib", unix))]
+mod tests {
+    use std::{env, fs, os::unix::fs::PermissionsExt};
+
+    use super::submit_with;
+    use crate::{
+        day,
+        template::{submissions::Verdict, PuzzleId},
+        year,
+    };
+
+    #[test]
+    fn captures_submit_output() {
+        let dir = env::temp_dir().join(format!("aoc-cli-mock-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let script = dir.join("aoc");
+        fs::write(
+            &script,
+            "#!/bin/sh\necho \"That's not the right answer; your answer is too high.\"\n",
+        )
+        .unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let output = submit_with(
+            script.to_str().unwrap(),
+            PuzzleId::new(year!(2024), day!(1)),
+            1,
+            "42",
+        )
+        .unwrap();
+        let verdict = Verdict::parse(&String::from_utf8_lossy(&output.stdout));
+
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(verdict, Verdict::TooHigh);
+    }
+}
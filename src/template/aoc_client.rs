@@ -0,0 +1,510 @@
+/// Built-in HTTP client for adventofcode.com. Used instead of aoc-cli when a session cookie is configured.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::{aoc_cli, PuzzleId};
+
+const BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust (advent_of_code template)";
+
+/// Minimum delay between two requests, shared between invocations.
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Environment variables that may hold the session cookie, in order of precedence.
+const SESSION_ENV_VARS: [&str; 2] = ["AOC_SESSION", "ADVENT_OF_CODE_SESSION"];
+
+/// Files (relative to the home directory) that may hold the session cookie. These match aoc-cli's lookup.
+const SESSION_FILES: [&str; 2] = [".adventofcode.session", ".config/adventofcode.session"];
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    BadStatus(u16, String),
+    Transport(String),
+    IO(io::Error),
+    Parser(String),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or write it to `~/.adventofcode.session`."
+            ),
+            AocClientError::BadStatus(status, url) => {
+                write!(f, "request to {url} failed with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+            AocClientError::Parser(e) => write!(f, "could not parse response: {e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, response) => {
+                AocClientError::BadStatus(status, response.get_url().to_string())
+            }
+            ureq::Error::Transport(t) => AocClientError::Transport(t.to_string()),
+        }
+    }
+}
+
+/// Returns the session cookie from the environment or from a session file in the home directory.
+pub fn find_session() -> Option<String> {
+    let from_env = SESSION_ENV_VARS
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|s| !s.trim().is_empty());
+
+    from_env
+        .or_else(|| {
+            let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+            SESSION_FILES
+                .iter()
+                .filter_map(|file| fs::read_to_string(PathBuf::from(&home).join(file)).ok())
+                .find(|s| !s.trim().is_empty())
+        })
+        .map(|s| s.trim().to_string())
+}
+
+/// Returns the built-in client, unless `AOC_CLIENT=aoc-cli` is set or no session cookie is configured.
+/// Callers fall back to aoc-cli if this returns `None`.
+pub fn native() -> Option<Client> {
+    if env::var("AOC_CLIENT").is_ok_and(|x| x == "aoc-cli") {
+        return None;
+    }
+    Client::from_env().ok()
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+    min_interval: Duration,
+    /// File that tracks the time of the last request, so the rate limit applies across invocations.
+    rate_limit_file: Option<PathBuf>,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent,
+            min_interval: MIN_REQUEST_INTERVAL,
+            rate_limit_file: Some(env::temp_dir().join("advent_of_code_last_request")),
+        }
+    }
+
+    /// Creates a client for adventofcode.com with the configured session cookie.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = find_session().ok_or(AocClientError::SessionNotFound)?;
+        Ok(Self::new(BASE_URL, &session))
+    }
+
+    /// Overrides the minimum delay between requests and where the time of the last request is tracked.
+    #[must_use]
+    pub fn with_rate_limit(mut self, min_interval: Duration, file: Option<PathBuf>) -> Self {
+        self.min_interval = min_interval;
+        self.rate_limit_file = file;
+        self
+    }
+
+    /// Fetches the puzzle input.
+    pub fn input(&self, id: PuzzleId) -> Result<String, AocClientError> {
+        let url = format!("{}/{}/input", self.base_url, puzzle_url(id));
+        self.throttle();
+        let response = self.agent.get(&url).set("Cookie", &self.cookie()).call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Fetches the puzzle description and converts it to markdown.
+    pub fn puzzle(&self, id: PuzzleId) -> Result<String, AocClientError> {
+        let url = format!("{}/{}", self.base_url, puzzle_url(id));
+        self.throttle();
+        let response = self.agent.get(&url).set("Cookie", &self.cookie()).call()?;
+
+        let html = response.into_string()?;
+        let articles = extract_articles(&html);
+
+        if articles.is_empty() {
+            return Err(AocClientError::Parser(
+                "puzzle page does not contain a description.".into(),
+            ));
+        }
+
+        Ok(articles
+            .iter()
+            .map(|a| html_to_markdown(a))
+            .collect::<Vec<_>>()
+            .join("\n\n"))
+    }
+
+    /// Submits an answer and returns the message of the response.
+    pub fn submit(&self, id: PuzzleId, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let url = format!("{}/{}/answer", self.base_url, puzzle_url(id));
+        self.throttle();
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        let html = response.into_string()?;
+        extract_articles(&html)
+            .first()
+            .map(|a| html_to_markdown(a))
+            .ok_or_else(|| AocClientError::Parser("response does not contain a message.".into()))
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn throttle(&self) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        if let Some(last) = self
+            .rate_limit_file
+            .as_ref()
+            .and_then(|file| fs::read_to_string(file).ok())
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .map(Duration::from_millis)
+        {
+            let next = last + self.min_interval;
+            if next > now {
+                thread::sleep(next - now);
+            }
+        }
+
+        if let Some(file) = &self.rate_limit_file {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            let _ = fs::write(file, now.as_millis().to_string());
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn puzzle_url(id: PuzzleId) -> String {
+    format!("{}/day/{}", id.year, id.day.into_inner())
+}
+
+/// Downloads input and puzzle description to the data directory.
+pub fn download(client: &Client, id: PuzzleId) -> Result<(), AocClientError> {
+    let input_path = aoc_cli::get_input_path(id);
+    let puzzle_path = aoc_cli::get_puzzle_path(id);
+
+    let input = client.input(id)?;
+    let puzzle = client.puzzle(id)?;
+
+    create_parent_dir(&input_path)?;
+    fs::write(&input_path, input)?;
+    create_parent_dir(&puzzle_path)?;
+    fs::write(&puzzle_path, puzzle)?;
+
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Downloads the puzzle description to the data directory and prints it.
+pub fn read(client: &Client, id: PuzzleId) -> Result<(), AocClientError> {
+    let puzzle = client.puzzle(id)?;
+    let puzzle_path = aoc_cli::get_puzzle_path(id);
+    create_parent_dir(&puzzle_path)?;
+    fs::write(puzzle_path, &puzzle)?;
+    println!("{puzzle}");
+    Ok(())
+}
+
+/// Submits an answer and returns the message of the response.
+pub fn submit(
+    client: &Client,
+    id: PuzzleId,
+    part: u8,
+    answer: &str,
+) -> Result<String, AocClientError> {
+    client.submit(id, part, answer)
+}
+
+fn create_parent_dir(path: &str) -> Result<(), io::Error> {
+    match PathBuf::from(path).parent() {
+        Some(parent) => fs::create_dir_all(parent),
+        None => Ok(()),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns the inner HTML of all `<article>` elements.
+fn extract_articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let content_start = start + open_end + 1;
+        let Some(end) = rest[content_start..].find("</article>") else {
+            break;
+        };
+        articles.push(&rest[content_start..content_start + end]);
+        rest = &rest[content_start + end..];
+    }
+
+    articles
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+fn get_attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let needle = format!("{name}=\"");
+    let start = tag.find(&needle)? + needle.len();
+    let end = tag[start..].find('"')?;
+    Some(&tag[start..start + end])
+}
+
+/// Converts the subset of HTML used in puzzle descriptions to markdown.
+fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut links: Vec<String> = vec![];
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            push_text(&mut out, rest, in_pre);
+            break;
+        };
+
+        push_text(&mut out, &rest[..tag_start], in_pre);
+
+        let Some(tag_len) = rest[tag_start..].find('>') else {
+            break;
+        };
+        let tag = &rest[tag_start + 1..tag_start + tag_len];
+        rest = &rest[tag_start + tag_len + 1..];
+
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+        let is_closing = tag.starts_with('/');
+
+        match (name, is_closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            ("em", _) if !in_pre => out.push('*'),
+            ("a", false) => {
+                links.push(get_attribute(tag, "href").unwrap_or_default().to_string());
+                out.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            ("li", false) => out.push_str("- "),
+            ("li", true) | ("br", _) => out.push('\n'),
+            _ => {}
+        }
+    }
+
+    while out.contains("\n\n\n") {
+        out = out.replace("\n\n\n", "\n\n");
+    }
+
+    out.trim_end().to_string()
+}
+
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    let text = decode_entities(text);
+    if in_pre {
+        out.push_str(&text);
+    } else if !(text.contains('\n') && text.trim().is_empty()) {
+        // NOTE: whitespace-only text containing line breaks is formatting between block elements.
+        out.push_str(&text.replace('\n', " "));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+        time::Duration,
+    };
+
+    use super::{html_to_markdown, AocClientError, Client};
+    use crate::{day, template::PuzzleId, year};
+
+    /// Serves the passed responses (status, body) in order and returns the received requests.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (Client, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+
+                let mut body_buf = vec![0; content_length];
+                reader.read_exact(&mut body_buf).unwrap();
+                request.push_str(&String::from_utf8_lossy(&body_buf));
+                requests.push(request);
+
+                let response = format!(
+                    "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+
+            requests
+        });
+
+        let client = Client::new(&url, "abc123").with_rate_limit(Duration::ZERO, None);
+        (client, handle)
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (client, handle) = serve(vec![(200, "1 2\n3 4\n")]);
+        let input = client.input(PuzzleId::new(year!(2024), day!(1))).unwrap();
+        let requests = handle.join().unwrap();
+
+        assert_eq!(input, "1 2\n3 4\n");
+        assert!(requests[0].starts_with("GET /2024/day/1/input "));
+        assert!(requests[0].contains("session=abc123"));
+    }
+
+    #[test]
+    fn downloads_puzzle_as_markdown() {
+        let (client, handle) = serve(vec![(
+            200,
+            "<html><main><article class=\"day-desc\"><h2>--- Day 1: Test ---</h2><p>Hello <em>world</em>.</p></article></main></html>",
+        )]);
+        let puzzle = client.puzzle(PuzzleId::new(year!(2024), day!(1))).unwrap();
+        handle.join().unwrap();
+
+        assert_eq!(puzzle, "## --- Day 1: Test ---\n\nHello *world*.");
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (client, handle) = serve(vec![(
+            200,
+            "<main><article><p>That's not the right answer; your answer is too high.</p></article></main>",
+        )]);
+        let message = client
+            .submit(PuzzleId::new(year!(2024), day!(5)), 2, "42")
+            .unwrap();
+        let requests = handle.join().unwrap();
+
+        assert_eq!(
+            message,
+            "That's not the right answer; your answer is too high."
+        );
+        assert!(requests[0].starts_with("POST /2024/day/5/answer "));
+        assert!(requests[0].ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn errors_on_bad_status() {
+        let (client, handle) = serve(vec![(404, "not found")]);
+        let result = client.input(PuzzleId::new(year!(2024), day!(25)));
+        handle.join().unwrap();
+
+        assert!(matches!(result, Err(AocClientError::BadStatus(404, _))));
+    }
+
+    #[test]
+    fn converts_html_to_markdown() {
+        let html = "<h2>--- Day 2 ---</h2>\n<p>See <a href=\"/about\">this</a> &amp; <code>x &lt; 3</code>:</p>\n<pre><code>1 2\n<em>3</em> 4\n</code></pre>\n<ul><li>one</li><li>two</li></ul>\n<p>Answer: <code><em>11</em></code>.</p>";
+        let expected = [
+            "## --- Day 2 ---",
+            "",
+            "See [this](/about) & `x < 3`:",
+            "",
+            "```",
+            "1 2",
+            "3 4",
+            "```",
+            "",
+            "- one",
+            "- two",
+            "",
+            "Answer: `*11*`.",
+        ]
+        .join("\n");
+
+        assert_eq!(html_to_markdown(html), expected);
+    }
+}
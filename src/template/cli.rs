@@ -0,0 +1,810 @@
+/// Declarative model of the command-line. Help texts and shell completions are generated from it, and the
+/// arguments passed to a command are parsed against it.
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::Error;
+
+/// Name of the binary, used to name the functions of the completion scripts.
+pub const BIN_NAME: &str = "advent_of_code";
+
+pub struct Command {
+    pub name: &'static str,
+    pub about: &'static str,
+    /// Positional arguments as shown in the usage, e.g. `<DAY>` or `[DAY]`.
+    pub positionals: &'static [&'static str],
+    pub options: &'static [Opt],
+}
+
+pub struct Opt {
+    pub flag: &'static str,
+    /// Name of the value of the option, e.g. `<N>`. Flags without a value are switches.
+    pub value: Option<&'static str>,
+    pub help: &'static str,
+    /// Values offered by shell completions.
+    pub choices: &'static [&'static str],
+}
+
+const fn switch(flag: &'static str, help: &'static str) -> Opt {
+    Opt {
+        flag,
+        value: None,
+        help,
+        choices: &[],
+    }
+}
+
+const fn value(flag: &'static str, value: &'static str, help: &'static str) -> Opt {
+    Opt {
+        flag,
+        value: Some(value),
+        help,
+        choices: &[],
+    }
+}
+
+const FORMAT: Opt = Opt {
+    choices: &["text", "json", "ndjson"],
+    ..value(
+        "--format",
+        "<FORMAT>",
+        "Output format: text, json or ndjson.",
+    )
+};
+
+const TIMEOUT: Opt = value(
+    "--timeout",
+    "<SECS>",
+    "Time the first run of a part may take before it is abandoned, defaults to 60.",
+);
+
+const RELEASE: Opt = switch(
+    "--release",
+    "Compiles with optimizations, defaults to `release` in aoc.toml.",
+);
+
+const NO_RELEASE: Opt = switch(
+    "--no-release",
+    "Compiles without optimizations, even if aoc.toml sets `release`.",
+);
+
+/// Options accepted by every command.
+pub const GLOBAL_OPTIONS: &[Opt] = &[
+    value(
+        "--year",
+        "<YEAR>",
+        "Year of the event, defaults to `year` in aoc.toml or `AOC_YEAR`.",
+    ),
+    switch("--help", "Prints help."),
+];
+
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "download",
+        about: "Downloads the input and description of a puzzle.",
+        positionals: &["<DAY>"],
+        options: &[],
+    },
+    Command {
+        name: "read",
+        about: "Reads the description of a puzzle in the terminal.",
+        positionals: &["<DAY>"],
+        options: &[],
+    },
+    Command {
+        name: "scaffold",
+        about: "Creates the solution, input and examples of a day from a template.",
+        positionals: &["<DAY>"],
+        options: &[
+            switch(
+                "--download",
+                "Downloads the puzzle first, to fill in its title and examples.",
+            ),
+            switch(
+                "--overwrite",
+                "Replaces existing files, keeping backups of their contents.",
+            ),
+            value(
+                "--template",
+                "<NAME>",
+                "Template in `templates/`, defaults to `template` in aoc.toml.",
+            ),
+            switch(
+                "--dry-run",
+                "Prints the planned changes without applying them.",
+            ),
+        ],
+    },
+    Command {
+        name: "examples",
+        about: "Extracts the examples of a downloaded puzzle description.",
+        positionals: &["<DAY>"],
+        options: &[
+            switch(
+                "--overwrite",
+                "Replaces existing examples, keeping backups of their contents.",
+            ),
+            switch(
+                "--dry-run",
+                "Prints the planned changes without applying them.",
+            ),
+        ],
+    },
+    Command {
+        name: "solve",
+        about: "Runs the solution of a day.",
+        positionals: &["<DAY>", "[EXAMPLE | -]"],
+        options: &[
+            RELEASE,
+            NO_RELEASE,
+            switch("--dhat", "Profiles the heap with dhat."),
+            Opt {
+                choices: &["1", "2"],
+                ..value("--submit", "<PART>", "Submits the answer of a part.")
+            },
+            FORMAT,
+            value(
+                "--input",
+                "<PATH>",
+                "Reads the input from a file, or stdin for `-`.",
+            ),
+            switch(
+                "--example",
+                "Runs an example instead of the input, `a` unless named.",
+            ),
+        ],
+    },
+    Command {
+        name: "profile",
+        about: "Profiles the heap of a day with dhat, and its CPU usage with perf if installed.",
+        positionals: &["<DAY>"],
+        options: &[Opt {
+            choices: &["1", "2"],
+            ..value("--part", "<PART>", "Profiles a single part.")
+        }],
+    },
+    Command {
+        name: "all",
+        about: "Runs the solutions of all days.",
+        positionals: &[],
+        options: &[
+            FORMAT,
+            TIMEOUT,
+            value(
+                "--jobs",
+                "<N>",
+                "Days to run in parallel, defaults to `jobs` in aoc.toml or 1.",
+            ),
+        ],
+    },
+    Command {
+        name: "time",
+        about: "Benchmarks the solutions of days without timings, or of a single day.",
+        positionals: &["[DAY]"],
+        options: &[
+            switch("--all", "Benchmarks all days."),
+            switch("--store", "Writes the timings to the readme."),
+            switch("--compare", "Compares the timings to the stored ones."),
+            value(
+                "--tolerance",
+                "<PERCENT>",
+                "Tolerated slowdown of `--compare`.",
+            ),
+            FORMAT,
+            TIMEOUT,
+            switch(
+                "--history",
+                "Shows the timings of a day across all stored runs.",
+            ),
+        ],
+    },
+    Command {
+        name: "verify",
+        about: "Checks the solutions against the stored answers.",
+        positionals: &["[DAY]"],
+        options: &[switch("--store", "Stores the answers of days without one.")],
+    },
+    Command {
+        name: "watch",
+        about: "Re-runs the examples and solution of a day when its files change.",
+        positionals: &["<DAY>"],
+        options: &[RELEASE, NO_RELEASE],
+    },
+    Command {
+        name: "today",
+        about: "Scaffolds, downloads and reads the puzzle of today.",
+        positionals: &[],
+        options: &[],
+    },
+    Command {
+        name: "completions",
+        about: "Prints the completion script of a shell for the cargo aliases.",
+        positionals: &["<bash | zsh | fish>"],
+        options: &[],
+    },
+    Command {
+        name: "help",
+        about: "Prints the help of a command.",
+        positionals: &["[COMMAND]"],
+        options: &[],
+    },
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(format!(
+                "unsupported shell `{s}`, expecting one of `bash`, `zsh` or `fish`."
+            )),
+        }
+    }
+}
+
+impl Display for Shell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Fish => "fish",
+        })
+    }
+}
+
+pub fn find_command(name: &str) -> Option<&'static Command> {
+    COMMANDS.iter().find(|command| command.name == name)
+}
+
+/// The arguments passed to a command, parsed against its options with [`Command::parse`].
+/// Reading an option that is not part of the command's model panics, so that the model can not drift from its use.
+pub struct Matches {
+    command: &'static Command,
+    switches: Vec<&'static str>,
+    values: Vec<(&'static str, String)>,
+    positionals: Vec<String>,
+}
+
+impl Matches {
+    fn check_option(&self, flag: &str) {
+        assert!(
+            self.command.find_option(flag).is_some(),
+            "`{flag}` is not an option of `{}` in the command-line model.",
+            self.command.name
+        );
+    }
+
+    /// Whether the switch was passed.
+    pub fn contains(&self, flag: &str) -> bool {
+        self.check_option(flag);
+        self.switches.contains(&flag)
+    }
+
+    /// Parses the value of an option, if it was passed.
+    pub fn value<T>(&self, flag: &str) -> Result<Option<T>, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.check_option(flag);
+        self.values
+            .iter()
+            .find(|(f, _)| *f == flag)
+            .map(|(_, value)| {
+                value
+                    .parse()
+                    .map_err(|e| Error::Usage(format!("invalid value `{value}` for `{flag}`: {e}")))
+            })
+            .transpose()
+    }
+
+    /// Parses the positional argument at `index`, if it was passed.
+    pub fn positional<T>(&self, index: usize) -> Result<Option<T>, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let name = self.command.positionals.get(index).unwrap_or_else(|| {
+            panic!(
+                "`{}` has no positional argument {index} in the command-line model.",
+                self.command.name
+            )
+        });
+
+        self.positionals
+            .get(index)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|e| Error::Usage(format!("invalid value `{value}` for {name}: {e}")))
+            })
+            .transpose()
+    }
+
+    /// Parses the positional argument at `index`, failing if it was not passed.
+    pub fn required<T>(&self, index: usize) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.positional(index)?.ok_or_else(|| {
+            let name = self.command.positionals[index].trim_matches(['<', '>', '[', ']']);
+            Error::Usage(format!("missing argument <{name}>."))
+        })
+    }
+}
+
+impl Command {
+    fn find_option(&self, flag: &str) -> Option<&Opt> {
+        self.options
+            .iter()
+            .chain(GLOBAL_OPTIONS)
+            .find(|opt| opt.flag == flag)
+    }
+
+    /// Parses the arguments passed to the command. Switches are passed as `--flag`, options as `--flag value` or
+    /// `--flag=value`. The value of an option is taken as is, even if it starts with `-`, e.g. `--tolerance -5`.
+    pub fn parse<S: AsRef<str>>(&'static self, args: &[S]) -> Result<Matches, Error> {
+        let mut matches = Matches {
+            command: self,
+            switches: vec![],
+            values: vec![],
+            positionals: vec![],
+        };
+        let mut args = args.iter().map(AsRef::as_ref);
+
+        while let Some(arg) = args.next() {
+            if arg == "-" || !arg.starts_with('-') {
+                matches.positionals.push(arg.to_string());
+                continue;
+            }
+
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) => (flag, Some(value)),
+                None if arg == "-h" => ("--help", None),
+                None => (arg, None),
+            };
+
+            let opt = self.find_option(flag).ok_or_else(|| {
+                Error::Usage(format!(
+                    "unknown option `{flag}` for `{}`. Run `cargo {} --help` to list its options.",
+                    self.name, self.name
+                ))
+            })?;
+
+            if matches.switches.contains(&opt.flag)
+                || matches.values.iter().any(|(flag, _)| *flag == opt.flag)
+            {
+                return Err(Error::Usage(format!(
+                    "option `{flag}` was passed more than once."
+                )));
+            }
+
+            match (opt.value, inline) {
+                (None, None) => matches.switches.push(opt.flag),
+                (None, Some(_)) => {
+                    return Err(Error::Usage(format!(
+                        "option `{flag}` does not take a value."
+                    )))
+                }
+                (Some(_), Some(value)) => matches.values.push((opt.flag, value.to_string())),
+                (Some(name), None) => {
+                    let value = args.next().ok_or_else(|| {
+                        Error::Usage(format!("option `{flag}` expects a value {name}."))
+                    })?;
+                    matches.values.push((opt.flag, value.to_string()));
+                }
+            }
+        }
+
+        if let Some(unexpected) = matches.positionals.get(self.positionals.len()..) {
+            if !unexpected.is_empty() {
+                let unexpected: Vec<String> =
+                    unexpected.iter().map(|arg| format!("`{arg}`")).collect();
+                return Err(Error::Usage(format!(
+                    "unexpected argument(s) {}.",
+                    unexpected.join(", ")
+                )));
+            }
+        }
+
+        Ok(matches)
+    }
+
+    /// Formats the help text of the command.
+    pub fn help(&self) -> String {
+        let mut usage = vec![format!("cargo {}", self.name)];
+        usage.extend(self.positionals.iter().map(|p| p.to_string()));
+        usage.push("[OPTIONS]".into());
+
+        let mut lines = vec![
+            self.about.to_string(),
+            String::new(),
+            format!("Usage: {}", usage.join(" ")),
+            String::new(),
+            "Options:".into(),
+        ];
+
+        let options: Vec<(String, &str)> = self
+            .options
+            .iter()
+            .chain(GLOBAL_OPTIONS)
+            .map(|opt| {
+                let name = match (opt.flag, opt.value) {
+                    ("--help", _) => "-h, --help".to_string(),
+                    (flag, Some(value)) => format!("{flag} {value}"),
+                    (flag, None) => flag.to_string(),
+                };
+                (name, opt.help)
+            })
+            .collect();
+
+        lines.extend(format_columns(&options));
+        lines.join("\n")
+    }
+}
+
+/// Formats the help text listing all commands.
+pub fn help() -> String {
+    let commands: Vec<(String, &str)> = COMMANDS
+        .iter()
+        .map(|command| (command.name.to_string(), command.about))
+        .collect();
+
+    let mut lines = vec![
+        "Solves Advent of Code puzzles.".to_string(),
+        String::new(),
+        "Usage: cargo <COMMAND> [OPTIONS]".into(),
+        String::new(),
+        "Commands:".into(),
+    ];
+
+    lines.extend(format_columns(&commands));
+    lines.push(String::new());
+    lines.push("Run `cargo <COMMAND> --help` for the options of a command. Defaults are read from aoc.toml.".into());
+    lines.join("\n")
+}
+
+fn format_columns(rows: &[(String, &str)]) -> Vec<String> {
+    let width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+
+    rows.iter()
+        .map(|(name, help)| format!("  {name:<width$}  {help}"))
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Generates the completion script of a shell for the commands, which run as cargo aliases, e.g. `cargo solve`.
+/// Other cargo commands are passed on to the completion of cargo, if it is installed.
+pub fn completions(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash_completions(),
+        Shell::Zsh => zsh_completions(),
+        Shell::Fish => fish_completions(),
+    }
+}
+
+/// Commands that are completed, all except `help`, which cargo provides itself.
+fn cargo_commands() -> impl Iterator<Item = &'static Command> {
+    COMMANDS.iter().filter(|command| command.name != "help")
+}
+
+fn all_options(command: &Command) -> impl Iterator<Item = &Opt> {
+    command.options.iter().chain(GLOBAL_OPTIONS)
+}
+
+/// Positional values offered by shell completions.
+fn positional_choices(command: &Command) -> Vec<&'static str> {
+    match command.name {
+        "completions" => vec!["bash", "zsh", "fish"],
+        _ => vec![],
+    }
+}
+
+fn bash_completions() -> String {
+    let names: Vec<&str> = cargo_commands().map(|c| c.name).collect();
+    let mut s = String::new();
+
+    s.push_str("# Completes the cargo aliases of advent_of_code. Load it after the completion of cargo, e.g. with\n");
+    s.push_str("# `source <(cargo completions bash)` in ~/.bashrc.\n");
+    s.push_str(&format!("if [[ -z ${{_{BIN_NAME}_cargo+x}} ]]; then\n"));
+    s.push_str("    declare -F _completion_loader >/dev/null && _completion_loader cargo\n");
+    s.push_str(&format!(
+        "    _{BIN_NAME}_cargo=$(complete -p cargo 2>/dev/null | sed -n 's/.*-F \\([^ ]*\\).*/\\1/p')\n"
+    ));
+    s.push_str("fi\n\n");
+
+    s.push_str(&format!("_{BIN_NAME}() {{\n"));
+    s.push_str("    local cur=\"${COMP_WORDS[COMP_CWORD]}\" prev=\"${COMP_WORDS[COMP_CWORD-1]}\" opts=\"\"\n\n");
+    s.push_str("    if [[ $COMP_CWORD -eq 1 ]]; then\n");
+    s.push_str(&format!(
+        "        [[ -n $_{BIN_NAME}_cargo ]] && \"$_{BIN_NAME}_cargo\" \"$@\"\n"
+    ));
+    s.push_str(&format!(
+        "        COMPREPLY+=($(compgen -W \"{}\" -- \"$cur\"))\n",
+        names.join(" ")
+    ));
+    s.push_str("        return\n    fi\n\n");
+
+    s.push_str("    case \"${COMP_WORDS[1]}\" in\n");
+    for command in cargo_commands() {
+        let words: Vec<&str> = all_options(command)
+            .map(|opt| opt.flag)
+            .chain(positional_choices(command))
+            .collect();
+        s.push_str(&format!(
+            "        {}) opts=\"{}\" ;;\n",
+            command.name,
+            words.join(" ")
+        ));
+    }
+    s.push_str(&format!(
+        "        *)\n            [[ -n $_{BIN_NAME}_cargo ]] && \"$_{BIN_NAME}_cargo\" \"$@\"\n            return ;;\n"
+    ));
+    s.push_str("    esac\n\n");
+
+    s.push_str("    case \"$prev\" in\n");
+    let mut seen = vec![];
+    for opt in cargo_commands().flat_map(|c| c.options) {
+        if !opt.choices.is_empty() && !seen.contains(&opt.flag) {
+            seen.push(opt.flag);
+            s.push_str(&format!(
+                "        {})\n            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n            return ;;\n",
+                opt.flag,
+                opt.choices.join(" ")
+            ));
+        }
+    }
+    s.push_str("    esac\n\n");
+
+    s.push_str("    COMPREPLY=($(compgen -W \"$opts\" -- \"$cur\"))\n}\n\n");
+    s.push_str(&format!("complete -F _{BIN_NAME} cargo\n"));
+    s
+}
+
+/// Escapes a description for zsh, where brackets and colons separate the parts of a spec.
+fn zsh_escape(s: &str) -> String {
+    s.replace('\'', "'\\''")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+        .replace(':', "\\:")
+}
+
+fn zsh_completions() -> String {
+    let mut s = String::new();
+
+    s.push_str(
+        "# Completes the cargo aliases of advent_of_code. Load it after `compinit`, e.g. with\n",
+    );
+    s.push_str("# `source <(cargo completions zsh)` in ~/.zshrc.\n");
+    s.push_str(&format!(
+        "_{BIN_NAME}() {{\n    local -a commands\n    commands=(\n"
+    ));
+
+    for command in cargo_commands() {
+        s.push_str(&format!(
+            "        '{}:{}'\n",
+            command.name,
+            zsh_escape(command.about)
+        ));
+    }
+    s.push_str("    )\n\n    if (( CURRENT == 2 )); then\n        _describe 'command' commands\n");
+    s.push_str("        (( $+functions[_cargo] )) && _cargo\n        return\n    fi\n\n");
+
+    s.push_str("    case $words[2] in\n");
+    for command in cargo_commands() {
+        s.push_str(&format!(
+            "        {})\n            _arguments",
+            command.name
+        ));
+        for opt in all_options(command) {
+            let action = match (opt.value, opt.choices) {
+                (None, _) => String::new(),
+                (Some(value), []) => format!(":{}:", zsh_escape(value)),
+                (Some(value), choices) => {
+                    format!(":{}:({})", zsh_escape(value), choices.join(" "))
+                }
+            };
+            s.push_str(&format!(
+                " \\\n                '{}[{}]{}'",
+                opt.flag,
+                zsh_escape(opt.help),
+                action
+            ));
+        }
+        let choices = positional_choices(command);
+        if !choices.is_empty() {
+            s.push_str(&format!(
+                " \\\n                '*: :({})'",
+                choices.join(" ")
+            ));
+        }
+        s.push_str("\n            ;;\n");
+    }
+    s.push_str("        *)\n            (( $+functions[_cargo] )) && _cargo\n            ;;\n");
+    s.push_str("    esac\n}\n\n");
+
+    // NOTE: the completion of cargo is autoloaded on first use, load it now so that it can be called.
+    s.push_str("(( $+functions[_cargo] )) || autoload -Uz +X _cargo 2>/dev/null || unfunction _cargo 2>/dev/null\n");
+    s.push_str(&format!("compdef _{BIN_NAME} cargo\n"));
+    s
+}
+
+fn fish_completions() -> String {
+    let fish_escape = |s: &str| s.replace('\\', "\\\\").replace('\'', "\\'");
+    let names: Vec<&str> = cargo_commands().map(|c| c.name).collect();
+
+    // NOTE: fish adds these to the completion of cargo, which keeps completing its other commands.
+    let mut s = String::new();
+    s.push_str("# Completes the cargo aliases of advent_of_code. Load it with `cargo completions fish | source` in\n");
+    s.push_str("# ~/.config/fish/config.fish, a file in the completions folder would replace the completion of cargo.\n");
+    s.push_str(&format!(
+        "complete -c cargo -n '__fish_seen_subcommand_from {}' -f\n",
+        names.join(" ")
+    ));
+
+    for command in cargo_commands() {
+        s.push_str(&format!(
+            "complete -c cargo -n __fish_use_subcommand -a {} -d '{}'\n",
+            command.name,
+            fish_escape(command.about)
+        ));
+    }
+
+    for command in cargo_commands() {
+        let condition = format!("'__fish_seen_subcommand_from {}'", command.name);
+
+        for opt in all_options(command) {
+            let mut line = format!(
+                "complete -c cargo -n {condition} -l {}",
+                opt.flag.trim_start_matches("--")
+            );
+            if opt.flag == "--help" {
+                line.push_str(" -s h");
+            }
+            if opt.value.is_some() {
+                line.push_str(" -r");
+            }
+            if !opt.choices.is_empty() {
+                line.push_str(&format!(" -a '{}'", opt.choices.join(" ")));
+            }
+            line.push_str(&format!(" -d '{}'\n", fish_escape(opt.help)));
+            s.push_str(&line);
+        }
+
+        let choices = positional_choices(command);
+        if !choices.is_empty() {
+            s.push_str(&format!(
+                "complete -c cargo -n {condition} -a '{}'\n",
+                choices.join(" ")
+            ));
+        }
+    }
+
+    s
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{completions, find_command, help, Shell, COMMANDS};
+
+    #[test]
+    fn parses_arguments() {
+        let solve = find_command("solve").unwrap();
+        let args = solve
+            .parse(&[
+                "1",
+                "--release",
+                "--format=json",
+                "--year",
+                "2024",
+                "-",
+                "-h",
+            ])
+            .unwrap();
+
+        assert!(args.contains("--release"));
+        assert!(args.contains("--help"));
+        assert!(!args.contains("--dhat"));
+        assert_eq!(
+            args.value::<String>("--format").unwrap(),
+            Some("json".into())
+        );
+        assert_eq!(args.value::<u16>("--year").unwrap(), Some(2024));
+        assert_eq!(args.required::<u8>(0).unwrap(), 1);
+        assert_eq!(args.positional::<String>(1).unwrap(), Some("-".into()));
+
+        assert!(solve.parse(&["1", "--jobs", "2"]).is_err());
+        assert!(find_command("watch")
+            .unwrap()
+            .parse(&["1", "--no-release"])
+            .unwrap()
+            .contains("--no-release"));
+        assert!(find_command("all").unwrap().parse(&["--jobs", "2"]).is_ok());
+    }
+
+    #[test]
+    fn parses_negative_values() {
+        let time = find_command("time").unwrap();
+        let args = time.parse(&["--tolerance", "-5", "--compare"]).unwrap();
+        assert_eq!(args.value::<f64>("--tolerance").unwrap(), Some(-5.0));
+        assert!(args.contains("--compare"));
+    }
+
+    #[test]
+    fn rejects_malformed_arguments() {
+        let time = find_command("time").unwrap();
+        assert!(time.parse(&["--tolerance"]).is_err());
+        assert!(time.parse(&["--store=yes"]).is_err());
+        assert!(time.parse(&["--store", "--store"]).is_err());
+        assert!(time.parse(&["1", "2"]).is_err());
+        assert!(time
+            .parse(&["--tolerance", "ten"])
+            .unwrap()
+            .value::<f64>("--tolerance")
+            .is_err());
+        assert!(time.parse::<&str>(&[]).unwrap().required::<u8>(0).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_options_outside_the_model() {
+        let all = find_command("all").unwrap().parse::<&str>(&[]).unwrap();
+        all.contains("--release");
+    }
+
+    #[test]
+    fn formats_help() {
+        let text = help();
+        for command in COMMANDS {
+            assert!(text.contains(command.name));
+        }
+
+        let scaffold = find_command("scaffold").unwrap().help();
+        assert!(scaffold.contains("Usage: cargo scaffold <DAY> [OPTIONS]"));
+        assert!(scaffold.contains("  --template <NAME>  Template in `templates/`"));
+        assert!(scaffold.contains("  -h, --help "));
+    }
+
+    #[test]
+    fn generates_completions() {
+        let bash = completions(Shell::Bash);
+        assert!(bash.contains("complete -F _advent_of_code cargo"));
+        assert!(bash.contains("        solve) opts=\"--release --no-release --dhat"));
+        assert!(!bash.contains("        help) opts="));
+        assert!(
+            bash.contains("--format)\n            COMPREPLY=($(compgen -W \"text json ndjson\"")
+        );
+
+        let zsh = completions(Shell::Zsh);
+        assert!(zsh.ends_with("compdef _advent_of_code cargo\n"));
+        assert!(zsh.contains(
+            "'--format[Output format\\: text, json or ndjson.]:<FORMAT>:(text json ndjson)'"
+        ));
+
+        let fish = completions(Shell::Fish);
+        assert!(
+            fish.contains("complete -c cargo -n '__fish_seen_subcommand_from scaffold' -l dry-run")
+        );
+        assert!(fish.contains("-l help -s h"));
+    }
+
+    #[test]
+    fn parses_shells() {
+        assert_eq!("zsh".parse::<Shell>(), Ok(Shell::Zsh));
+        assert!("powershell".parse::<Shell>().is_err());
+    }
+}
@@ -1,5 +1,28 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::time::Duration;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::output::OutputFormat;
+use crate::template::runner::{RunOptions, Solution};
+use crate::template::{all_days, run_multi::run_multi, Error, Year};
+
+pub fn handle(
+    solutions: &[Solution],
+    year: Year,
+    format: OutputFormat,
+    timeout: Duration,
+    jobs: usize,
+) -> Result<(), Error> {
+    let options = RunOptions {
+        is_timed: false,
+        format,
+        timeout: Some(timeout),
+        jobs,
+        part: None,
+        repeat: None,
+    };
+    let report = run_multi(solutions, year, &all_days(year).collect(), &options);
+
+    match report.failures() {
+        0 => Ok(()),
+        failures => Err(Error::Failures(failures)),
+    }
 }
@@ -1,14 +1,20 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{aoc_cli, aoc_client, get_year_path, Error, PuzzleId};
+use std::fs;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+/// Downloads the input and description of a puzzle, with the native client or `aoc-cli`.
+pub fn handle(id: PuzzleId) -> Result<(), Error> {
+    if let Some(client) = aoc_client::native() {
+        return Ok(aoc_client::download(&client, id)?);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    aoc_cli::check().map_err(|_| Error::MissingClient)?;
+
+    // NOTE: aoc-cli does not create missing directories.
+    for folder in ["inputs", "puzzles"] {
+        fs::create_dir_all(get_year_path(id.year).join(folder))
+            .map_err(|e| Error::IO("failed to create data directory".into(), e))?;
+    }
+
+    aoc_cli::download(id)?;
+    Ok(())
 }
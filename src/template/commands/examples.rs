@@ -0,0 +1,119 @@
+use std::io;
+
+use crate::template::aoc_cli::get_puzzle_path;
+use crate::template::plan::Plan;
+use crate::template::puzzle::{self, PuzzleExample};
+use crate::template::{Error, PuzzleId};
+
+const EXPECTED_TEMPLATE: &str = "# Expected answers of the example, one per part, e.g. `1: 42`.\n";
+
+pub fn handle(id: PuzzleId, overwrite: bool, dry_run: bool) -> Result<(), Error> {
+    let Some(markdown) = puzzle::read_markdown(id) else {
+        return Err(Error::Parse(format!(
+            "puzzle description \"{}\" not found. Try running `cargo download {}` first.",
+            get_puzzle_path(id),
+            id.day
+        )));
+    };
+
+    let examples = puzzle::extract_examples(&markdown);
+    if examples.is_empty() {
+        return Err(Error::Parse(format!(
+            "no examples found in \"{}\".",
+            get_puzzle_path(id)
+        )));
+    }
+
+    let mut changes = Plan::default();
+    plan(id, &examples, overwrite, &mut changes);
+
+    if !dry_run {
+        changes
+            .apply()
+            .map_err(|e| Error::IO("failed to write examples".into(), io::Error::other(e)))?;
+    }
+
+    for line in changes.describe(dry_run) {
+        println!("{line}");
+    }
+
+    Ok(())
+}
+
+/// Returns the names of the examples, `a`, `b`, … There is always an example `a`, to be filled in by hand if needed.
+pub fn names(examples: &[PuzzleExample]) -> Vec<String> {
+    ('a'..='z')
+        .take(examples.len().max(1))
+        .map(String::from)
+        .collect()
+}
+
+/// Plans to write the examples to `DD-a.txt`, `DD-b.txt`, … next to their expected answers. Without any examples,
+/// plans an empty `DD-a`. Files with content are kept unless `overwrite` is set. The examples directory is created
+/// if it is missing.
+pub fn plan(id: PuzzleId, examples: &[PuzzleExample], overwrite: bool, plan: &mut Plan) {
+    plan.add_dir("data directory", format!("data/{}/examples", id.year));
+
+    for (i, name) in names(examples).iter().enumerate() {
+        let example = examples.get(i).cloned().unwrap_or_default();
+
+        plan.add(
+            "example file",
+            format!("data/{}/examples/{}-{name}.txt", id.year, id.day),
+            example.input.clone(),
+            overwrite,
+            is_blank,
+        );
+
+        plan.add(
+            "expected answers file",
+            format!("data/{}/examples/{}-{name}.expected", id.year, id.day),
+            expected_contents(&example),
+            overwrite,
+            is_blank_expected,
+        );
+    }
+}
+
+fn expected_contents(example: &PuzzleExample) -> String {
+    let mut s = EXPECTED_TEMPLATE.to_string();
+    for (part, answer) in &example.answers {
+        s.push_str(&format!("{part}: {answer}\n"));
+    }
+    s
+}
+
+/// Whether an example or input is empty.
+pub fn is_blank(s: &str) -> bool {
+    s.trim().is_empty()
+}
+
+/// Whether an expected answers file has no content besides comments.
+fn is_blank_expected(s: &str) -> bool {
+    s.lines()
+        .map(str::trim)
+        .all(|line| line.is_empty() || line.starts_with('#'))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{expected_contents, EXPECTED_TEMPLATE};
+    use crate::template::examples::parse_expected;
+    use crate::template::puzzle::PuzzleExample;
+
+    #[test]
+    fn writes_expected_answers() {
+        let contents = expected_contents(&PuzzleExample {
+            input: String::new(),
+            answers: vec![(1, "7".into()), (2, "5".into())],
+        });
+
+        assert!(contents.starts_with(EXPECTED_TEMPLATE));
+        assert_eq!(
+            parse_expected(&contents, &[]).unwrap().answers,
+            vec![(1, "7".to_string()), (2, "5".to_string())]
+        );
+    }
+}
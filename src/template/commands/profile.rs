@@ -0,0 +1,151 @@
+/// Profiles the parts of a day: the heap with dhat, and the CPU with `perf` if it is installed.
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::{env, fs, io, process};
+
+use crate::template::heap_profile::{HeapSummary, DHAT_FILE_NAME};
+use crate::template::{flamegraph, get_year_path, Error, PuzzleId};
+
+/// Seconds a part is repeated while `perf` samples it. A single run of most parts is too short to be sampled.
+const CPU_PROFILE_SECS: &str = "3";
+
+pub fn handle(id: PuzzleId, part: Option<u8>) -> Result<(), Error> {
+    let parts = part.map_or(vec![1, 2], |part| vec![part]);
+
+    // NOTE: both profiles use the `dhat` profile, but only the heap profile enables the `dhat-heap` feature.
+    // Running all heap profiles first avoids recompiling the solution between parts.
+    for &part in &parts {
+        profile_heap(id, part)?;
+    }
+
+    if Command::new("perf").arg("--version").output().is_err() {
+        eprintln!("Warning: `perf` not found, skipping the CPU flamegraphs.");
+        return Ok(());
+    }
+
+    let status = Command::new("cargo")
+        .args(["build", "--quiet", "--profile", "dhat", "--bin"])
+        .arg(id.to_string())
+        .status()
+        .map_err(|e| Error::IO("failed to run cargo".into(), e))?;
+    if !status.success() {
+        return Err(Error::Solution(status.code()));
+    }
+
+    for part in parts {
+        match profile_cpu(id, part) {
+            Ok(path) => println!("Flamegraph of part {part}: {}", path.display()),
+            Err(e) => eprintln!("Warning: could not profile the CPU of part {part}: {e}"),
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs a part with the dhat allocator and prints a summary of its heap profile.
+fn profile_heap(id: PuzzleId, part: u8) -> Result<(), Error> {
+    // NOTE: the profile is only written if the solution has the part, a stale one must not be summarised.
+    match fs::remove_file(DHAT_FILE_NAME) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => {
+            return Err(Error::IO(format!("failed to remove {DHAT_FILE_NAME}"), e))
+        }
+        _ => {}
+    }
+
+    let status = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--profile",
+            "dhat",
+            "--features",
+            "dhat-heap",
+            "--bin",
+        ])
+        .arg(id.to_string())
+        .args(["--", "--part", &part.to_string()])
+        .status()
+        .map_err(|e| Error::IO("failed to run cargo".into(), e))?;
+    if !status.success() {
+        return Err(Error::Solution(status.code()));
+    }
+
+    let profile = match fs::read_to_string(DHAT_FILE_NAME) {
+        Ok(profile) => profile,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            eprintln!("Warning: no heap profile was written for part {part}.");
+            return Ok(());
+        }
+        Err(e) => return Err(Error::IO(format!("failed to read {DHAT_FILE_NAME}"), e)),
+    };
+
+    let summary = HeapSummary::parse(&profile)
+        .map_err(|e| Error::Parse(format!("could not parse {DHAT_FILE_NAME}: {e}")))?;
+    println!("{summary}");
+
+    Ok(())
+}
+
+/// Records a part with `perf` and renders the samples as a flamegraph. Returns the path of the SVG.
+/// The part is repeated for a few seconds, so that short parts are sampled too.
+fn profile_cpu(id: PuzzleId, part: u8) -> Result<PathBuf, String> {
+    let target_dir = env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".into());
+    let binary = PathBuf::from(target_dir).join("dhat").join(id.to_string());
+    let data = env::temp_dir().join(format!("aoc-{id}-{part}-{}.perf.data", process::id()));
+
+    // NOTE: release builds omit frame pointers, so stacks are unwound from DWARF debug info.
+    let status = Command::new("perf")
+        .args([
+            "record",
+            "--quiet",
+            "-F",
+            "999",
+            "--call-graph",
+            "dwarf",
+            "-o",
+        ])
+        .arg(&data)
+        .arg("--")
+        .arg(&binary)
+        .args(["--part", &part.to_string(), "--repeat", CPU_PROFILE_SECS])
+        .stdout(Stdio::null())
+        .status()
+        .map_err(|e| format!("failed to run perf: {e}"))?;
+    if !status.success() {
+        let _ = fs::remove_file(&data);
+        return Err(
+            "`perf record` failed. Check that `kernel.perf_event_paranoid` allows profiling."
+                .into(),
+        );
+    }
+
+    let output = Command::new("perf")
+        .arg("script")
+        .arg("-i")
+        .arg(&data)
+        .stderr(Stdio::null())
+        .output();
+    let _ = fs::remove_file(&data);
+    let output = output.map_err(|e| format!("failed to run perf: {e}"))?;
+    if !output.status.success() {
+        return Err("`perf script` failed.".into());
+    }
+
+    let stacks = flamegraph::fold(&String::from_utf8_lossy(&output.stdout));
+    if stacks.is_empty() {
+        return Err("perf did not record any samples.".into());
+    }
+
+    let folder = get_year_path(id.year).join("profiles");
+    let path = folder.join(format!("{}-{part}.svg", id.day));
+    fs::create_dir_all(&folder)
+        .and_then(|()| {
+            fs::write(
+                &path,
+                flamegraph::render(&stacks, &format!("{id} part {part}")),
+            )
+        })
+        .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+
+    Ok(path)
+}
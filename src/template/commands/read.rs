@@ -1,15 +1,18 @@
-use std::process;
+use std::fs;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_cli, aoc_client, get_year_path, Error, PuzzleId};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+pub fn handle(id: PuzzleId) -> Result<(), Error> {
+    if let Some(client) = aoc_client::native() {
+        return Ok(aoc_client::read(&client, id)?);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    aoc_cli::check().map_err(|_| Error::MissingClient)?;
+
+    // NOTE: aoc-cli does not create missing directories.
+    fs::create_dir_all(get_year_path(id.year).join("puzzles"))
+        .map_err(|e| Error::IO("failed to create data directory".into(), e))?;
+
+    aoc_cli::read(id)?;
+    Ok(())
 }
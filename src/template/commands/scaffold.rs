@@ -1,79 +1,180 @@
-use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
-};
+/// Creates the module, input and examples of a day from a template. Templates can use the placeholders
+/// `%DAY_NUMBER%`, `%YEAR%`, `%TITLE%`, `%EXAMPLES%` (the names of the examples, e.g. `a, b`) and
+/// `%EXAMPLE_PART_ONE%`, `%EXAMPLE_PART_TWO%` (the expected answers of the examples). The title and examples are
+/// read from the puzzle description if it was downloaded.
+use std::{fmt::Display, fs, path::Path};
 
-use crate::template::Day;
+use crate::template::commands::examples;
+use crate::template::plan::{Plan, PlanError};
+use crate::template::puzzle::{self, PuzzleExample};
+use crate::template::{Error, PuzzleId, Year};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
-    } else {
-        file.create_new(true);
+/// Directory of the project's templates, e.g. `templates/grid.txt` for `scaffold DD --template grid`.
+/// A `templates/default.txt` replaces the built-in template.
+const TEMPLATE_DIR: &str = "templates";
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// The template does not exist. Holds the available templates.
+    TemplateNotFound(String, Vec<String>),
+    /// The module exists and `--overwrite` was not passed.
+    ModuleExists(String),
+    Plan(PlanError),
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScaffoldError::TemplateNotFound(name, available) if available.is_empty() => {
+                write!(f, "template \"{name}\" not found in \"{TEMPLATE_DIR}\".")
+            }
+            ScaffoldError::TemplateNotFound(name, available) => write!(
+                f,
+                "template \"{name}\" not found in \"{TEMPLATE_DIR}\", available templates: {}.",
+                available.join(", ")
+            ),
+            ScaffoldError::ModuleExists(path) => write!(
+                f,
+                "module file \"{path}\" already exists. Pass `--overwrite` to replace it."
+            ),
+            ScaffoldError::Plan(e) => write!(f, "{e}"),
+        }
     }
-    file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+impl std::error::Error for ScaffoldError {}
+
+/// Reads a template from the project's template directory, falling back to the built-in template.
+fn load_template(name: Option<&str>) -> Result<String, ScaffoldError> {
+    let dir = Path::new(TEMPLATE_DIR);
+
+    match name {
+        None => Ok(fs::read_to_string(dir.join("default.txt"))
+            .unwrap_or_else(|_| MODULE_TEMPLATE.to_string())),
+        Some(name) => fs::read_to_string(dir.join(format!("{name}.txt")))
+            .map_err(|_| ScaffoldError::TemplateNotFound(name.into(), list_templates(dir))),
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+fn list_templates(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    match path.extension() {
+                        Some(ext) if ext == "txt" => {
+                            Some(path.file_stem()?.to_string_lossy().to_string())
+                        }
+                        _ => None,
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
+    names.sort();
+    names
+}
+
+/// Replaces the placeholders of a template.
+fn render(
+    template: &str,
+    id: PuzzleId,
+    title: Option<&str>,
+    extracted: &[PuzzleExample],
+) -> String {
+    let answer = |part: u8| {
+        extracted
+            .iter()
+            .flat_map(|example| &example.answers)
+            .find(|(p, _)| *p == part)
+            .map_or("unknown".to_string(), |(_, answer)| format!("`{answer}`"))
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
+    let day = id.day.into_inner().to_string();
+
+    template
+        .replace("%DAY_NUMBER%", &day)
+        .replace("%YEAR%", &id.year.to_string())
+        .replace("%TITLE%", title.unwrap_or(&format!("Day {day}")))
+        .replace("%EXAMPLES%", &examples::names(extracted).join(", "))
+        .replace("%EXAMPLE_PART_ONE%", &answer(1))
+        .replace("%EXAMPLE_PART_TWO%", &answer(2))
+}
+
+/// Plans the files of the day and applies them, or only prints them if `dry_run` is set. Existing inputs and
+/// examples are never truncated: files with content are kept, or backed up if `overwrite` is set.
+pub fn handle(
+    id: PuzzleId,
+    overwrite: bool,
+    template: Option<&str>,
+    dry_run: bool,
+) -> Result<(), Error> {
+    let day = id.day;
+    let module_path = format!("src/bin/{id}.rs");
+    let template = load_template(template)?;
+
+    if !overwrite && Path::new(&module_path).exists() {
+        return Err(ScaffoldError::ModuleExists(module_path).into());
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    let markdown = puzzle::read_markdown(id);
+    let title = markdown.as_deref().and_then(puzzle::title);
+    let extracted = markdown
+        .as_deref()
+        .map(puzzle::extract_examples)
+        .unwrap_or_default();
+
+    let mut plan = Plan::default();
+    for folder in ["inputs", "examples", "puzzles"] {
+        plan.add_dir("data directory", format!("data/{}/{folder}", id.year));
     }
+    plan.add(
+        "module file",
+        &module_path,
+        render(&template, id, title.as_deref(), &extracted),
+        overwrite,
+        examples::is_blank,
+    );
+    // NOTE: the input is only ever created, it is downloaded and never replaced by scaffolding.
+    plan.add(
+        "input file",
+        format!("data/{}/inputs/{day}.txt", id.year),
+        String::new(),
+        false,
+        examples::is_blank,
+    );
+    examples::plan(id, &extracted, overwrite, &mut plan);
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    if dry_run {
+        for line in plan.describe(true) {
+            println!("{line}");
         }
+        println!("---");
+        println!("Dry run, no files were changed.");
+        return Ok(());
+    }
+
+    plan.apply().map_err(ScaffoldError::Plan)?;
+
+    for line in plan.describe(false) {
+        println!("{line}");
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if Year::from_env() == Some(id.year) {
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    } else {
+        println!(
+            "🎄 Type `cargo solve {day} --year {}` to run your solution.",
+            id.year
+        );
+    }
+
+    Ok(())
 }
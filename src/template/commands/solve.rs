@@ -1,9 +1,18 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::output::OutputFormat;
+use crate::template::{Error, PuzzleId};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    id: PuzzleId,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    format: Option<OutputFormat>,
+    input: Option<String>,
+    example: Option<Option<String>>,
+) -> Result<(), Error> {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), id.to_string()];
 
     if dhat {
         cmd_args.extend([
@@ -23,12 +32,31 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    if let Some(format) = format {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.to_string());
+    }
+
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input);
+    }
+
+    if let Some(example) = example {
+        cmd_args.push("--example".to_string());
+        cmd_args.extend(example);
+    }
+
+    let status = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .status()
+        .map_err(|e| Error::IO("failed to run cargo".into(), e))?;
 
-    cmd.wait().unwrap();
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::Solution(status.code()))
+    }
 }
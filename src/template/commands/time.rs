@@ -1,19 +1,41 @@
 use std::collections::HashSet;
+use std::time::Duration;
 
+use crate::template::history::{format_timestamp, History, HistoryEntry};
+use crate::template::output::OutputFormat;
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::runner::{RunOptions, Solution};
+use crate::template::stats::nanos_to_duration;
+use crate::template::timings::{Comparison, Timings, Verdict};
+use crate::template::{all_days, readme_benchmarks, Day, Error, Year, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Default relative tolerance used when comparing against stored timings.
+pub const DEFAULT_TOLERANCE_PERCENT: f64 = 10.0;
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    solutions: &[Solution],
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    compare: Option<f64>,
+    format: OutputFormat,
+    timeout: Duration,
+) -> Result<(), Error> {
+    if cfg!(debug_assertions) {
+        eprintln!("Warning: benchmarking a debug build. Use `cargo time` to get release timings.");
+    }
+
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
-            if run_all {
-                all_days().collect()
+            if run_all || compare.is_some() {
+                all_days(year).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
+                all_days(year)
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
@@ -21,20 +43,147 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let options = RunOptions {
+        is_timed: true,
+        format,
+        timeout: Some(timeout),
+        jobs: 1,
+        part: None,
+        repeat: None,
+    };
+    let report = run_multi(solutions, year, &days_to_run, &options);
+    let failures = report.failures();
+    let timings = report.timings.unwrap_or_default();
+
+    // NOTE: every run is kept in the history, regardless of whether it is stored as the current timings.
+    if !timings.data.is_empty() {
+        if let Err(e) = History::append(year, &HistoryEntry::new(timings.clone())) {
+            eprintln!("Failed to append to benchmark history: {e}");
+        }
+    }
+
+    if let Some(tolerance_percent) = compare {
+        let comparisons = stored_timings.compare(&timings, tolerance_percent / 100.0);
+        if format.is_text() {
+            print_comparisons(&comparisons, tolerance_percent);
+        }
+
+        let regressions = comparisons
+            .iter()
+            .filter(|c| c.verdict == Verdict::Regressed)
+            .count();
+
+        if regressions > 0 {
+            return Err(Error::Timing(format!(
+                "{regressions} part(s) regressed, not storing timings."
+            )));
+        }
+    }
+
+    // NOTE: timings of a run with failing parts are incomplete, they must not replace the stored ones.
+    if failures > 0 {
+        return Err(Error::Failures(failures));
+    }
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings
+            .store_file(year)
+            .map_err(|e| Error::IO("failed to store timings".into(), e))?;
 
-        println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
-            }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
-            }
+        readme_benchmarks::update(year, merged_timings)?;
+
+        if format.is_text() {
+            println!();
+            println!("Stored updated benchmarks.");
         }
     }
+
+    Ok(())
+}
+
+/// Prints how the timings of a day changed across the runs in the benchmark history.
+pub fn handle_history(year: Year, day: Day) -> Result<(), Error> {
+    let trend = History::read_from_file(year).trend(day);
+
+    if trend.is_empty() {
+        println!(
+            "No benchmark history for day {day} of {year}. Run `cargo time {day}` to record one."
+        );
+        return Ok(());
+    }
+
+    println!("{ANSI_BOLD}History of day {day} ({year}){ANSI_RESET}");
+    println!("------");
+
+    let mut previous: [Option<f64>; 2] = [None, None];
+
+    for point in &trend {
+        let parts: Vec<String> = [point.part_1, point.part_2]
+            .iter()
+            .zip(&mut previous)
+            .map(|(current, previous)| {
+                let str = format_part(*current, *previous);
+                if current.is_some() {
+                    *previous = *current;
+                }
+                str
+            })
+            .collect();
+
+        println!(
+            "{} {} {}: part 1 {} | part 2 {}",
+            format_timestamp(point.timestamp),
+            point.commit.as_deref().unwrap_or("-"),
+            point.machine,
+            parts[0],
+            parts[1]
+        );
+    }
+
+    Ok(())
+}
+
+/// Formats the median of a part and its change against the previous run, e.g. `1.2ms (-20.0%)`.
+fn format_part(current: Option<f64>, previous: Option<f64>) -> String {
+    match (current, previous) {
+        (Some(current), Some(previous)) if previous > 0.0 => format!(
+            "{:.1?} ({:+.1}%)",
+            nanos_to_duration(current),
+            (current / previous - 1.0) * 100.0
+        ),
+        (Some(current), _) => format!("{:.1?}", nanos_to_duration(current)),
+        (None, _) => "-".into(),
+    }
+}
+
+fn print_comparisons(comparisons: &[Comparison], tolerance_percent: f64) {
+    println!();
+    println!("{ANSI_BOLD}Comparison (tolerance ±{tolerance_percent}%){ANSI_RESET}");
+    println!("------");
+
+    for comparison in comparisons {
+        let current = nanos_to_duration(comparison.current);
+
+        let line = match (comparison.stored, comparison.change()) {
+            (Some(stored), Some(change)) => format!(
+                "{:.1?} → {current:.1?} ({:+.1}%)",
+                nanos_to_duration(stored),
+                change * 100.0
+            ),
+            _ => format!("{current:.1?}"),
+        };
+
+        let verdict = match comparison.verdict {
+            Verdict::Regressed => format!("{ANSI_BOLD}regressed{ANSI_RESET}"),
+            Verdict::Improved => "improved".into(),
+            Verdict::Unchanged => "unchanged".into(),
+            Verdict::New => "no stored timing".into(),
+        };
+
+        println!(
+            "Day {} Part {}: {line} {verdict}",
+            comparison.day, comparison.part
+        );
+    }
 }
@@ -0,0 +1,89 @@
+use std::collections::HashSet;
+
+use crate::template::answers::{Answers, Outcome, Verification};
+use crate::template::run_multi::run_multi;
+use crate::template::runner::{RunOptions, Solution, DEFAULT_TIMEOUT};
+use crate::template::{all_days, readme_stars, Day, Error, Year, ANSI_BOLD, ANSI_RESET};
+
+pub fn handle(
+    solutions: &[Solution],
+    year: Year,
+    day: Option<Day>,
+    store: bool,
+) -> Result<(), Error> {
+    let mut answers = Answers::read_from_file(year);
+
+    let days_to_run = day.map_or_else(|| all_days(year).collect(), |day| HashSet::from([day]));
+
+    let options = RunOptions {
+        timeout: Some(DEFAULT_TIMEOUT),
+        ..RunOptions::default()
+    };
+    let report = run_multi(solutions, year, &days_to_run, &options);
+    let verifications = answers.verify(&report.results);
+
+    print_verifications(&verifications);
+
+    if store {
+        let mut stored = 0;
+
+        // only record answers for parts that have none yet, stored answers are never overwritten.
+        for verification in &verifications {
+            if let (Outcome::Missing, Some(actual)) = (verification.outcome, &verification.actual) {
+                answers.set(verification.day, verification.part, actual.clone());
+                stored += 1;
+            }
+        }
+
+        if stored > 0 {
+            answers
+                .store_file(year)
+                .map_err(|e| Error::IO("failed to store answers".into(), e))?;
+            println!("Stored {stored} new answer(s).");
+        }
+    }
+
+    // NOTE: the stars table covers the whole event, it is only updated when all days were verified.
+    // Without any stored answers, e.g. in a fresh checkout, an existing table is left alone.
+    if day.is_none() && !answers.data.is_empty() {
+        // NOTE: verify again, answers stored above count as verified.
+        if readme_stars::update(year, &answers.verify(&report.results)).is_err() {
+            eprintln!("Failed to update the stars table in README.");
+        }
+    }
+
+    let failures = verifications
+        .iter()
+        .filter(|v| v.outcome == Outcome::Fail)
+        .count();
+
+    if failures > 0 {
+        return Err(Error::Verification(failures));
+    }
+
+    Ok(())
+}
+
+fn print_verifications(verifications: &[Verification]) {
+    println!();
+    println!("{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("------");
+
+    for verification in verifications {
+        let outcome = match verification.outcome {
+            Outcome::Pass => "✔ pass".to_string(),
+            Outcome::Fail => format!(
+                "{ANSI_BOLD}✖ fail{ANSI_RESET} (expected {}, got {})",
+                verification.expected.as_deref().unwrap_or("-"),
+                verification.actual.as_deref().unwrap_or("-")
+            ),
+            Outcome::Missing => "? missing answer".to_string(),
+            Outcome::Skipped => "- not run".to_string(),
+        };
+
+        println!(
+            "Day {} Part {}: {outcome}",
+            verification.day, verification.part
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use std::{env, fs};
+
+use tinyjson::JsonValue;
+
+use crate::template::{get_year_path, Error, PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Interval at which the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Modification times of the watched files, keyed by path.
+type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+/// Answers of a run, keyed by part.
+type Answers = HashMap<u8, Option<String>>;
+
+/// Runs until interrupted, re-running the day whenever its files change.
+pub fn handle(id: PuzzleId, release: bool) -> Result<(), Error> {
+    let mut snapshot = take_snapshot(id);
+    let mut previous: Option<Answers> = None;
+
+    loop {
+        println!("{ANSI_BOLD}Running {id}{ANSI_RESET}");
+        println!("------");
+
+        if run_tests(id) {
+            println!("Examples: ok");
+        } else {
+            println!("Examples: {ANSI_BOLD}failed{ANSI_RESET}");
+        }
+
+        if let Some(answers) = run_solution(id, release, previous.as_ref()) {
+            previous = Some(answers);
+        }
+
+        println!();
+        println!("{ANSI_ITALIC}Watching for changes, press Ctrl+C to stop.{ANSI_RESET}");
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let next = take_snapshot(id);
+            if next != snapshot {
+                snapshot = next;
+                break;
+            }
+        }
+
+        println!();
+    }
+}
+
+/// Collects the modification times of the day's solution, the shared library and the day's inputs and examples.
+fn take_snapshot(id: PuzzleId) -> Snapshot {
+    let src = env::current_dir().unwrap().join("src");
+    let mut paths = vec![src.join("bin").join(format!("{id}.rs")), src.join("lib.rs")];
+
+    for folder in ["inputs", "examples"] {
+        paths.extend(day_files(&get_year_path(id.year).join(folder), id));
+    }
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Lists the files of a folder that belong to the day, e.g. `01.txt`, `01-2.txt` or `01-a.expected`.
+fn day_files(folder: &Path, id: PuzzleId) -> Vec<PathBuf> {
+    let prefix = id.day.to_string();
+
+    fs::read_dir(folder)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| is_day_file(name, &prefix))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn is_day_file(name: &str, prefix: &str) -> bool {
+    name.strip_prefix(prefix)
+        .is_some_and(|rest| rest.starts_with('.') || rest.starts_with('-'))
+}
+
+/// Runs the tests of the day, including its examples. Returns whether they passed.
+fn run_tests(id: PuzzleId) -> bool {
+    Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &id.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::inherit())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Runs the solution and prints its answers, compared to the answers of the previous run.
+fn run_solution(id: PuzzleId, release: bool, previous: Option<&Answers>) -> Option<Answers> {
+    let mut cmd_args = vec!["run", "--quiet", "--bin"];
+    let id_str = id.to_string();
+    cmd_args.push(&id_str);
+
+    if release {
+        cmd_args.push("--release");
+    }
+
+    cmd_args.extend(["--", "--format", "json"]);
+
+    let output = match Command::new("cargo")
+        .args(&cmd_args)
+        .stderr(Stdio::inherit())
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to run solution: {e}");
+            return None;
+        }
+    };
+
+    if !output.status.success() {
+        println!("Solution: {ANSI_BOLD}failed{ANSI_RESET}");
+        return None;
+    }
+
+    let records = parse_records(&String::from_utf8_lossy(&output.stdout))?;
+    let mut answers = Answers::new();
+
+    for (part, answer, status) in records {
+        // NOTE: the first run has nothing to compare against.
+        let change = previous
+            .map(|p| describe_change(p.get(&part).and_then(Option::as_ref), answer.as_ref()))
+            .unwrap_or_default();
+
+        match &answer {
+            Some(answer) => println!("Part {part}: {ANSI_BOLD}{answer}{ANSI_RESET}{change}"),
+            None => println!("Part {part}: ✖ {status}{change}"),
+        }
+
+        answers.insert(part, answer);
+    }
+
+    Some(answers)
+}
+
+/// Reads the part, answer and status of the records written by `--format json`.
+fn parse_records(output: &str) -> Option<Vec<(u8, Option<String>, String)>> {
+    let json = output.parse::<JsonValue>().ok()?;
+    let records = json.get::<Vec<JsonValue>>()?;
+
+    records
+        .iter()
+        .map(|record| {
+            let record = record.get::<HashMap<String, JsonValue>>()?;
+            let part = *record.get("part")?.get::<f64>()? as u8;
+            let answer = record.get("answer")?.get::<String>().cloned();
+            let status = record.get("status")?.get::<String>()?.clone();
+            Some((part, answer, status))
+        })
+        .collect()
+}
+
+/// Describes how an answer differs from the answer of the previous run.
+fn describe_change(previous: Option<&String>, current: Option<&String>) -> String {
+    match (previous, current) {
+        (Some(previous), Some(current)) if previous == current => " (unchanged)".into(),
+        (Some(previous), _) => format!(" (was {previous})"),
+        (None, Some(_)) => " (new)".into(),
+        (None, None) => String::new(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{describe_change, is_day_file, parse_records};
+
+    #[test]
+    fn matches_day_files() {
+        assert!(is_day_file("01.txt", "01"));
+        assert!(is_day_file("01-2.txt", "01"));
+        assert!(is_day_file("01-a.expected", "01"));
+        assert!(!is_day_file("011.txt", "01"));
+        assert!(!is_day_file("02.txt", "01"));
+    }
+
+    #[test]
+    fn parses_json_records() {
+        let records = parse_records(
+            r#"[{"part": 1, "answer": "11", "status": "solved"}, {"part": 2, "answer": null, "status": "no_answer"}]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            records,
+            vec![
+                (1, Some("11".to_string()), "solved".to_string()),
+                (2, None, "no_answer".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn describes_answer_changes() {
+        let (eleven, twelve) = ("11".to_string(), "12".to_string());
+        assert_eq!(
+            describe_change(Some(&eleven), Some(&eleven)),
+            " (unchanged)"
+        );
+        assert_eq!(describe_change(Some(&eleven), Some(&twelve)), " (was 11)");
+        assert_eq!(describe_change(Some(&eleven), None), " (was 11)");
+        assert_eq!(describe_change(None, Some(&eleven)), " (new)");
+        assert_eq!(describe_change(None, None), "");
+    }
+}
@@ -0,0 +1,175 @@
+/// Project defaults of the command-line, read from `aoc.toml` in the project directory.
+///
+/// The file supports a flat subset of TOML. Flags passed on the command-line take precedence:
+///
+/// ```toml
+/// year = 2024        # instead of `AOC_YEAR`
+/// release = true     # `solve` and `watch` compile with optimizations, see `--no-release`
+/// jobs = 4           # days that `all` runs in parallel
+/// template = "grid"  # template of `scaffold`, see `templates/`
+/// ```
+use std::fmt::Display;
+use std::fs;
+use std::io;
+
+use crate::template::Year;
+
+pub const CONFIG_FILE_NAME: &str = "aoc.toml";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    pub year: Option<Year>,
+    pub release: bool,
+    pub jobs: Option<usize>,
+    pub template: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The line could not be parsed. Holds the line number and a description.
+    Parser(usize, String),
+    IO(io::Error),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Parser(line, e) => write!(f, "{CONFIG_FILE_NAME}:{line}: {e}"),
+            ConfigError::IO(e) => write!(f, "could not read {CONFIG_FILE_NAME}: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// Reads `aoc.toml` from the current directory. Returns the defaults if there is none.
+    pub fn load() -> Result<Self, ConfigError> {
+        match fs::read_to_string(CONFIG_FILE_NAME) {
+            Ok(s) => Self::parse(&s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(ConfigError::IO(e)),
+        }
+    }
+
+    /// The year of commands that are run without `--year`: `year` of `aoc.toml`, then `AOC_YEAR`.
+    pub fn default_year(&self) -> Option<Year> {
+        self.year.or_else(Year::from_env)
+    }
+
+    pub fn parse(s: &str) -> Result<Self, ConfigError> {
+        let mut config = Config::default();
+
+        for (i, line) in s.lines().enumerate() {
+            let error = |e: String| ConfigError::Parser(i + 1, e);
+
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error(format!("expected `key = value`, found `{line}`.")))?;
+            let (key, value) = (key.trim(), value.trim());
+
+            match key {
+                "year" => {
+                    config.year = Some(
+                        value
+                            .parse()
+                            .map_err(|_| error(format!("invalid year `{value}`.")))?,
+                    );
+                }
+                "release" => {
+                    config.release = value.parse().map_err(|_| {
+                        error(format!("expected `true` or `false`, found `{value}`."))
+                    })?;
+                }
+                "jobs" => {
+                    let jobs = value.parse().ok().filter(|&jobs: &usize| jobs > 0);
+                    config.jobs = Some(
+                        jobs.ok_or_else(|| error(format!("invalid number of jobs `{value}`.")))?,
+                    );
+                }
+                "template" => {
+                    config.template = Some(
+                        value
+                            .strip_prefix('"')
+                            .and_then(|v| v.strip_suffix('"'))
+                            .ok_or_else(|| {
+                                error(format!("expected a quoted string, found `{value}`."))
+                            })?
+                            .to_string(),
+                    );
+                }
+                _ => return Err(error(format!("unknown key `{key}`."))),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+/// Removes a trailing `# comment`, unless the `#` is part of a string.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+
+    line
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Config, ConfigError};
+    use crate::year;
+
+    #[test]
+    fn parses_config() {
+        let config = Config::parse(
+            "# defaults\nyear = 2023\nrelease = true # always\n\njobs = 4\ntemplate = \"grid#2\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                year: Some(year!(2023)),
+                release: true,
+                jobs: Some(4),
+                template: Some("grid#2".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_empty_config() {
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        for (s, line) in [
+            ("year = 2023\nyears = 2024", 2),
+            ("release = yes", 1),
+            ("jobs = 0", 1),
+            ("template = grid", 1),
+            ("year", 1),
+            ("year = 2014", 1),
+        ] {
+            match Config::parse(s) {
+                Err(ConfigError::Parser(l, _)) => assert_eq!(l, line, "{s}"),
+                other => panic!("expected parser error for {s:?}, got {other:?}"),
+            }
+        }
+    }
+}
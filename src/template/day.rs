@@ -5,10 +5,16 @@ use std::str::FromStr;
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
+use crate::template::Year;
+
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// The latest day of december a puzzle can be released on. The actual range is configured per [`Year`].
+pub const MAX_DAY: u8 = 31;
+
+/// A valid day number of advent (i.e. an integer in range 1 to 31).
+/// Use [`Year::has_day`] to check if the day is part of an event.
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -25,7 +31,7 @@ impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > MAX_DAY {
             return None;
         }
         Some(Self(day))
@@ -45,12 +51,13 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
-    pub fn today() -> Option<Self> {
+    /// Returns the current day of december if it's a day of the passed year's event, `None` otherwise.
+    pub fn today(year: Year) -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
+        let day = Self::new(u8::try_from(today.day()).ok()?)?;
+        if today.month() == 12 && year.has_day(day) {
+            Some(day)
         } else {
             None
         }
@@ -94,26 +101,29 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        f.write_str("expecting a day number between 1 and 31")
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
-pub fn all_days() -> AllDays {
-    AllDays::new()
+/// An iterator that yields every day of a year's event, from the 1st to its last day.
+pub fn all_days(year: Year) -> AllDays {
+    AllDays::new(year.day_count())
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the passed last day.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { current: 1 }
+    pub fn new(last: u8) -> Self {
+        Self {
+            current: 1,
+            last: last.min(MAX_DAY),
+        }
     }
 }
 
@@ -121,10 +131,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above `MAX_DAY`.
         let day = Day(self.current);
         self.current += 1;
 
@@ -139,11 +149,11 @@ impl Iterator for AllDays {
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $day != 0 && $day <= $crate::template::MAX_DAY,
             concat!(
                 "invalid day number `",
                 $day,
-                "`, expecting a value between 1 and 25"
+                "`, expecting a value between 1 and 31"
             ),
         );
         $crate::template::Day::__new_unchecked($day)
@@ -155,10 +165,11 @@ macro_rules! day {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{all_days, Day};
+    use crate::year;
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = all_days(year!(2024));
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -187,6 +198,19 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn all_days_iterator_respects_day_count() {
+        assert_eq!(all_days(year!(2025)).count(), 12);
+        assert_eq!(all_days(year!(2025)).last(), Some(Day(12)));
+    }
+
+    #[test]
+    fn parses_days_past_the_25th() {
+        assert_eq!("26".parse::<Day>().unwrap(), Day(26));
+        assert!("32".parse::<Day>().is_err());
+        assert!("0".parse::<Day>().is_err());
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,179 @@
+/// The error type of the template's commands. Commands return it instead of exiting, `main` maps it to an exit code.
+use std::fmt::Display;
+use std::io;
+
+use crate::template::aoc_cli::AocCommandError;
+use crate::template::aoc_client::AocClientError;
+use crate::template::commands::scaffold::ScaffoldError;
+use crate::template::config::ConfigError;
+use crate::template::input::InputError;
+use crate::template::readme_benchmarks;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The command-line arguments are invalid.
+    Usage(String),
+    /// A file could not be parsed, e.g. `aoc.toml` or a puzzle description.
+    Parse(String),
+    /// A file operation failed. Holds what was attempted, e.g. `could not store answers`.
+    IO(String, io::Error),
+    /// Neither a session cookie nor aoc-cli is configured.
+    MissingClient,
+    AocCli(AocCommandError),
+    AocClient(AocClientError),
+    Input(InputError),
+    Scaffold(ScaffoldError),
+    /// Benchmarks regressed or could not be stored.
+    Timing(String),
+    Readme(readme_benchmarks::Error),
+    /// Holds the number of parts that did not match their stored answers.
+    Verification(usize),
+    /// Holds the number of parts that failed, crashed or timed out in a multi-day run.
+    Failures(usize),
+    /// A solution binary failed. Holds its exit code, if it was not terminated by a signal.
+    Solution(Option<i32>),
+}
+
+/// Exit codes of failed solution binaries are offset by this, so that they do not clash with the other kinds.
+const SOLUTION_EXIT_CODE_BASE: i32 = 100;
+
+impl Error {
+    /// Returns the exit code of the error kind.
+    ///
+    /// | Code | Kind |
+    /// | --- | --- |
+    /// | 2 | `Usage` |
+    /// | 3 | `Parse` |
+    /// | 4 | `IO` |
+    /// | 5 | `MissingClient`, `AocCli`, `AocClient` |
+    /// | 6 | `Input` |
+    /// | 7 | `Scaffold` |
+    /// | 8 | `Timing` |
+    /// | 9 | `Readme` |
+    /// | 10 | `Verification` |
+    /// | 11 | `Failures` |
+    /// | 100 | `Solution` that was terminated |
+    /// | 101-255 | `Solution` that exited with code 1-155, offset by 100 |
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Usage(_) => 2,
+            Error::Parse(_) => 3,
+            Error::IO(_, _) => 4,
+            Error::MissingClient | Error::AocCli(_) | Error::AocClient(_) => 5,
+            Error::Input(_) => 6,
+            Error::Scaffold(_) => 7,
+            Error::Timing(_) => 8,
+            Error::Readme(_) => 9,
+            Error::Verification(_) => 10,
+            Error::Failures(_) => 11,
+            // NOTE: codes outside of the range can not be offset, they are reported as terminated.
+            Error::Solution(code) => {
+                SOLUTION_EXIT_CODE_BASE
+                    + code
+                        .filter(|code| (1..=255 - SOLUTION_EXIT_CODE_BASE).contains(code))
+                        .unwrap_or(0)
+            }
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage(e) | Error::Parse(e) | Error::Timing(e) => write!(f, "{e}"),
+            Error::IO(action, e) => write!(f, "{action}: {e}"),
+            Error::MissingClient => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it, or configure a session cookie in `AOC_SESSION`."
+            ),
+            Error::AocCli(e) => write!(f, "failed to call aoc-cli: {e}"),
+            Error::AocClient(e) => write!(f, "{e}"),
+            Error::Input(e) => write!(f, "{e}"),
+            Error::Scaffold(e) => write!(f, "{e}"),
+            Error::Readme(e) => write!(f, "could not update the readme: {e}"),
+            Error::Verification(failures) => {
+                write!(f, "{failures} part(s) did not match the stored answers.")
+            }
+            Error::Failures(failures) => {
+                write!(f, "{failures} part(s) failed, crashed or timed out.")
+            }
+            Error::Solution(Some(code)) => write!(f, "solution exited with code {code}."),
+            Error::Solution(None) => write!(f, "solution was terminated."),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::IO(_, e) => Some(e),
+            Error::Scaffold(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<AocCommandError> for Error {
+    fn from(e: AocCommandError) -> Self {
+        Error::AocCli(e)
+    }
+}
+
+impl From<AocClientError> for Error {
+    fn from(e: AocClientError) -> Self {
+        Error::AocClient(e)
+    }
+}
+
+impl From<ConfigError> for Error {
+    fn from(e: ConfigError) -> Self {
+        Error::Parse(e.to_string())
+    }
+}
+
+impl From<InputError> for Error {
+    fn from(e: InputError) -> Self {
+        Error::Input(e)
+    }
+}
+
+impl From<ScaffoldError> for Error {
+    fn from(e: ScaffoldError) -> Self {
+        Error::Scaffold(e)
+    }
+}
+
+impl From<readme_benchmarks::Error> for Error {
+    fn from(e: readme_benchmarks::Error) -> Self {
+        Error::Readme(e)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::io;
+
+    use super::Error;
+
+    #[test]
+    fn maps_exit_codes() {
+        let errors = [
+            Error::Usage(String::new()),
+            Error::Parse(String::new()),
+            Error::IO(String::new(), io::Error::other("")),
+            Error::MissingClient,
+            Error::Timing(String::new()),
+            Error::Verification(1),
+            Error::Failures(1),
+        ];
+        let codes: Vec<i32> = errors.iter().map(Error::exit_code).collect();
+        assert_eq!(codes, vec![2, 3, 4, 5, 8, 10, 11]);
+
+        assert_eq!(Error::Solution(Some(6)).exit_code(), 106);
+        assert_eq!(Error::Solution(Some(101)).exit_code(), 201);
+        assert_eq!(Error::Solution(Some(200)).exit_code(), 100);
+        assert_eq!(Error::Solution(None).exit_code(), 100);
+    }
+}
@@ -0,0 +1,224 @@
+/// Checks solutions against a numbered set of examples with expected answers.
+///
+/// Examples are stored as `data/YYYY/examples/DD-a.txt`, `DD-b.txt`, … next to their expected answers in
+/// `DD-a.expected`, `DD-b.expected`, … An expected answers file lists one answer per part and can override
+/// [parameters](crate::template::params) of the puzzle:
+///
+/// ```text
+/// # lines starting with `#` are ignored.
+/// grid_size = 7
+/// 1: 22
+/// 2: 6,1
+/// ```
+use std::fs;
+use std::path::PathBuf;
+
+use crate::template::{get_data_path, get_year_path, params, PuzzleId};
+
+/// A part of a solution that returns its answer as a string, or the chain of error messages if it failed.
+pub type ExamplePart = (u8, fn(&str) -> Result<Option<String>, Vec<String>>);
+
+/// Returns the path of an example's input, e.g. `data/2024/examples/01-a.txt`. Example `a` falls back to the
+/// day's single example of older layouts, e.g. `data/2024/examples/01.txt`.
+pub fn get_example_path(id: PuzzleId, name: &str) -> PathBuf {
+    let path = get_year_path(id.year)
+        .join("examples")
+        .join(format!("{}-{name}.txt", id.day));
+
+    if name == "a" && !path.exists() {
+        let legacy = get_data_path("examples", id);
+        if legacy.exists() {
+            return legacy;
+        }
+    }
+
+    path
+}
+
+/// Returns the path of an example's expected answers, e.g. `data/2024/examples/01-a.expected`.
+pub fn get_expected_path(id: PuzzleId, name: &str) -> PathBuf {
+    get_year_path(id.year)
+        .join("examples")
+        .join(format!("{}-{name}.expected", id.day))
+}
+
+/// The contents of an expected answers file.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Expected {
+    /// Pairs of part and answer.
+    pub answers: Vec<(u8, String)>,
+    /// Pairs of parameter name and value.
+    pub params: Vec<(String, String)>,
+}
+
+/// Reads an example's input to a string.
+#[must_use]
+pub fn read_example(id: PuzzleId, name: &str) -> String {
+    let path = get_example_path(id, name);
+    fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("could not open example {}: {e}", path.display()))
+}
+
+/// Parses an expected answers file. Fails for parameters that are not in `params`, the names declared by the solution.
+pub fn parse_expected(s: &str, params: &[&str]) -> Result<Expected, String> {
+    let mut expected = Expected::default();
+
+    for line in s
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    {
+        // NOTE: answers are checked first, they may contain a `=`.
+        if let Some((part, answer)) = line.split_once(':') {
+            if let Ok(part) = part.trim().parse::<u8>() {
+                expected.answers.push((part, answer.trim().to_string()));
+                continue;
+            }
+        }
+
+        let (name, value) = line.split_once('=').ok_or_else(|| {
+            format!("expected `<part>: <answer>` or `<name> = <value>`, got `{line}`.")
+        })?;
+
+        let name = name.trim();
+        if !params.contains(&name) {
+            return Err(if params.is_empty() {
+                format!("unknown parameter `{name}`, the solution does not declare parameters.")
+            } else {
+                format!(
+                    "unknown parameter `{name}`, expected one of `{}`.",
+                    params.join("`, `")
+                )
+            });
+        }
+
+        expected
+            .params
+            .push((name.to_string(), value.trim().to_string()));
+    }
+
+    Ok(expected)
+}
+
+/// Runs the parts of a solution against an example and panics if an answer does not match.
+/// Parts without an expected answer are not checked.
+pub fn check(id: PuzzleId, name: &str, parts: &[ExamplePart], params: &[&str]) {
+    let expected_path = get_expected_path(id, name);
+
+    let input = read_example(id, name);
+    let expected = fs::read_to_string(&expected_path)
+        .map_err(|e| e.to_string())
+        .and_then(|s| parse_expected(&s, params))
+        .unwrap_or_else(|e| panic!("could not read {}: {e}", expected_path.display()));
+
+    if expected.answers.is_empty() {
+        eprintln!("Example {name} of day {} has no expected answers.", id.day);
+    }
+
+    let overrides: Vec<(&str, &str)> = expected
+        .params
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+
+    for (part, answer) in &expected.answers {
+        let Some((_, func)) = parts.iter().find(|(p, _)| p == part) else {
+            continue;
+        };
+
+        let actual = params::with_overrides(&overrides, || func(&input))
+            .unwrap_or_else(|e| panic!("part {part} of example {name} failed: {}", e.join(": ")));
+
+        assert_eq!(
+            actual.as_ref(),
+            Some(answer),
+            "part {part} of example {name} returned a wrong answer."
+        );
+    }
+}
+
+/// Generates a test case for each of the passed examples, e.g. `examples!(a, b)` checks `DD-a.txt` and `DD-b.txt`.
+/// Must be used in a solution file that calls [`solution!`](crate::solution).
+// NOTE: `crate` intentionally refers to the solution binary, which defines `PUZZLE`, `PARTS` and `PARAM_NAMES`.
+#[allow(clippy::crate_in_macro_def)]
+#[macro_export]
+macro_rules! examples {
+    ($( $name:ident ),+ $(,)?) => {
+        mod examples {
+            $(
+                #[test]
+                fn $name() {
+                    $crate::template::examples::check(
+                        crate::PUZZLE,
+                        stringify!($name),
+                        crate::PARTS,
+                        crate::PARAM_NAMES,
+                    );
+                }
+            )+
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{get_example_path, parse_expected};
+    use crate::{day, template::PuzzleId, year};
+
+    #[test]
+    fn falls_back_to_single_examples() {
+        let path = |day, name| get_example_path(PuzzleId::new(year!(2024), day), name);
+        assert!(path(day!(1), "a").ends_with("examples/01.txt"));
+        assert!(path(day!(1), "b").ends_with("examples/01-b.txt"));
+        assert!(path(day!(14), "a").ends_with("examples/14-a.txt"));
+    }
+
+    #[test]
+    fn parses_expected_answers() {
+        let expected = parse_expected("# comment\n1: 11\n\n2:  abc,def \n", &[]).unwrap();
+        assert_eq!(
+            expected.answers,
+            vec![(1, "11".to_string()), (2, "abc,def".to_string())]
+        );
+        assert!(expected.params.is_empty());
+    }
+
+    #[test]
+    fn parses_expected_params() {
+        let expected =
+            parse_expected("grid_size = 7\nbytes=12\n1: 22\n", &["grid_size", "bytes"]).unwrap();
+        assert_eq!(
+            expected.params,
+            vec![
+                ("grid_size".to_string(), "7".to_string()),
+                ("bytes".to_string(), "12".to_string())
+            ]
+        );
+        assert_eq!(expected.answers, vec![(1, "22".to_string())]);
+    }
+
+    #[test]
+    fn parses_empty_expected_answers() {
+        assert_eq!(
+            parse_expected("# nothing yet\n", &[]).unwrap(),
+            Default::default()
+        );
+    }
+
+    #[test]
+    fn errors_for_malformed_expected_answers() {
+        assert!(parse_expected("11", &[]).is_err());
+        assert!(parse_expected("one: 11", &[]).is_err());
+    }
+
+    #[test]
+    fn errors_for_unknown_params() {
+        assert_eq!(
+            parse_expected("grid = 7\n1: 22\n", &["grid_size", "bytes"]),
+            Err("unknown parameter `grid`, expected one of `grid_size`, `bytes`.".into())
+        );
+        assert!(parse_expected("grid_size = 7\n", &[]).is_err());
+    }
+}
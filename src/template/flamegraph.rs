@@ -0,0 +1,261 @@
+/// Renders CPU profiles recorded with `perf` as flamegraph SVGs.
+///
+/// The samples printed by `perf script` are folded into stacks, e.g. `main;part_one;parse 12`, and drawn with
+/// the outermost frame at the bottom. The width of a frame is its share of the samples.
+use std::collections::BTreeMap;
+
+const WIDTH: f64 = 1200.0;
+const FRAME_HEIGHT: f64 = 16.0;
+const TITLE_HEIGHT: f64 = 30.0;
+/// Approximate width of a character of the labels, used to truncate them.
+const CHAR_WIDTH: f64 = 7.0;
+/// Frames narrower than this are not drawn.
+const MIN_WIDTH: f64 = 0.5;
+
+#[derive(Debug, Default)]
+struct Frame {
+    samples: u64,
+    children: BTreeMap<String, Frame>,
+}
+
+impl Frame {
+    fn insert(&mut self, stack: &[&str], samples: u64) {
+        self.samples += samples;
+        if let Some((name, rest)) = stack.split_first() {
+            self.children
+                .entry((*name).to_string())
+                .or_default()
+                .insert(rest, samples);
+        }
+    }
+}
+
+/// Folds the samples printed by `perf script` into stacks, outermost frame first, with their number of samples.
+pub fn fold(perf_script: &str) -> BTreeMap<String, u64> {
+    let mut stacks = BTreeMap::new();
+    let mut frames: Vec<&str> = vec![];
+
+    // NOTE: a sample is a header line followed by indented frames, innermost first, and ends with a blank line.
+    for line in perf_script.lines().chain([""]) {
+        if line.trim().is_empty() {
+            if !frames.is_empty() {
+                frames.reverse();
+                *stacks.entry(frames.join(";")).or_insert(0) += 1;
+                frames.clear();
+            }
+        } else if line.starts_with(char::is_whitespace) {
+            frames.push(parse_symbol(line.trim()));
+        }
+    }
+
+    stacks
+}
+
+/// Extracts the symbol of a frame, e.g. `part_one` from `55d5c1a2b3c4 part_one+0x24 (/path/to/2024_01)`.
+fn parse_symbol(frame: &str) -> &str {
+    let symbol = frame.split_once(' ').map_or(frame, |(_, symbol)| symbol);
+    let symbol = symbol
+        .rsplit_once(" (")
+        .map_or(symbol, |(symbol, _)| symbol);
+
+    match symbol.rsplit_once("+0x") {
+        Some((symbol, offset)) if offset.chars().all(|c| c.is_ascii_hexdigit()) => symbol,
+        _ => symbol,
+    }
+}
+
+/// Draws the folded stacks as a flamegraph.
+pub fn render(stacks: &BTreeMap<String, u64>, title: &str) -> String {
+    let mut root = Frame::default();
+    for (stack, &samples) in stacks {
+        let stack: Vec<&str> = stack.split(';').collect();
+        root.insert(&stack, samples);
+    }
+
+    let height = TITLE_HEIGHT + FRAME_HEIGHT * (depth(&root) + 1) as f64;
+
+    let mut lines = vec![
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" font-family="monospace" font-size="11">"#
+        ),
+        format!(
+            r#"  <text x="{}" y="20" text-anchor="middle" font-size="14">{}</text>"#,
+            WIDTH / 2.0,
+            escape(title)
+        ),
+    ];
+
+    if root.samples > 0 {
+        draw(&mut lines, "all", &root, root.samples, 0.0, 0, height);
+    }
+
+    lines.push("</svg>".into());
+    lines.join("\n") + "\n"
+}
+
+fn depth(frame: &Frame) -> usize {
+    frame
+        .children
+        .values()
+        .map(|child| depth(child) + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+fn draw(
+    lines: &mut Vec<String>,
+    name: &str,
+    frame: &Frame,
+    total: u64,
+    x: f64,
+    level: usize,
+    height: f64,
+) {
+    let width = frame.samples as f64 / total as f64 * WIDTH;
+    if width < MIN_WIDTH {
+        return;
+    }
+
+    let y = height - FRAME_HEIGHT * (level + 1) as f64;
+    let share = frame.samples as f64 / total as f64 * 100.0;
+
+    lines.push(format!(
+        r#"  <rect x="{x:.1}" y="{y}" width="{width:.1}" height="{}" fill="{}" rx="2"><title>{} ({} samples, {share:.2}%)</title></rect>"#,
+        FRAME_HEIGHT - 1.0,
+        color(name),
+        escape(name),
+        frame.samples
+    ));
+
+    if let Some(label) = truncate(name, width) {
+        lines.push(format!(
+            r#"  <text x="{:.1}" y="{}">{}</text>"#,
+            x + 3.0,
+            y + FRAME_HEIGHT - 4.0,
+            escape(&label)
+        ));
+    }
+
+    let mut child_x = x;
+    for (child_name, child) in &frame.children {
+        draw(lines, child_name, child, total, child_x, level + 1, height);
+        child_x += child.samples as f64 / total as f64 * WIDTH;
+    }
+}
+
+/// Shortens a label to the width of its frame. Returns `None` if not even a few characters fit.
+fn truncate(name: &str, width: f64) -> Option<String> {
+    let fits = ((width - 6.0) / CHAR_WIDTH).max(0.0) as usize;
+    let length = name.chars().count();
+
+    if length <= fits {
+        Some(name.to_string())
+    } else if fits >= 4 {
+        Some(name.chars().take(fits - 2).collect::<String>() + "..")
+    } else {
+        None
+    }
+}
+
+/// Picks a warm color from the name of a frame, so that a function has the same color across flamegraphs.
+fn color(name: &str) -> String {
+    // FNV-1a
+    let hash = name.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+
+    let red = 205 + hash % 50;
+    let green = (hash >> 8) % 230;
+    let blue = (hash >> 16) % 55;
+    format!("rgb({red},{green},{blue})")
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{fold, parse_symbol, render, truncate};
+
+    const PERF_SCRIPT: &str = "\
+2024_01 4242 [003] 1234.567890:     250000 cpu-clock:u:
+\t    55d5c1a2b3c4 _2024_01::parse+0x24 (/root/crate/target/dhat/2024_01)
+\t    55d5c1a2b000 _2024_01::part_one+0x10 (/root/crate/target/dhat/2024_01)
+\t    7f0000000000 main+0x80 (/root/crate/target/dhat/2024_01)
+
+2024_01 4242 [003] 1234.568140:     250000 cpu-clock:u:
+\t    55d5c1a2b3c4 _2024_01::parse+0x2c (/root/crate/target/dhat/2024_01)
+\t    55d5c1a2b000 _2024_01::part_one+0x10 (/root/crate/target/dhat/2024_01)
+\t    7f0000000000 main+0x80 (/root/crate/target/dhat/2024_01)
+
+2024_01 4242 [003] 1234.568390:     250000 cpu-clock:u:
+\t    55d5c1a2b100 <core::str::iter::Lines as core::iter::traits::iterator::Iterator>::next+0x8 (/root/crate/target/dhat/2024_01)
+\t    55d5c1a2b000 _2024_01::part_one+0x30 (/root/crate/target/dhat/2024_01)
+\t    7f0000000000 main+0x80 (/root/crate/target/dhat/2024_01)
+";
+
+    #[test]
+    fn folds_samples() {
+        let stacks = fold(PERF_SCRIPT);
+
+        assert_eq!(
+            stacks,
+            BTreeMap::from([
+                (
+                    "main;_2024_01::part_one;<core::str::iter::Lines as core::iter::traits::iterator::Iterator>::next".to_string(),
+                    1
+                ),
+                ("main;_2024_01::part_one;_2024_01::parse".to_string(), 2),
+            ])
+        );
+    }
+
+    #[test]
+    fn parses_symbols() {
+        assert_eq!(
+            parse_symbol("55d5c1a2b3c4 part_one+0x24 (/bin/2024_01)"),
+            "part_one"
+        );
+        assert_eq!(
+            parse_symbol("7f0000000000 [unknown] ([unknown])"),
+            "[unknown]"
+        );
+        assert_eq!(
+            parse_symbol("7f0000000000 memcpy (/usr/lib/libc.so.6)"),
+            "memcpy"
+        );
+    }
+
+    #[test]
+    fn renders_frames() {
+        let svg = render(&fold(PERF_SCRIPT), "2024_01 part 1");
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains("<title>all (3 samples, 100.00%)</title>"));
+        assert!(svg.contains("<title>_2024_01::parse (2 samples, 66.67%)</title>"));
+        assert!(svg.contains("&lt;core::str::iter::Lines as"));
+        assert!(!svg.contains("<core::"));
+    }
+
+    #[test]
+    fn renders_empty_profile() {
+        let svg = render(&BTreeMap::new(), "empty");
+        assert!(!svg.contains("<rect"));
+    }
+
+    #[test]
+    fn truncates_labels() {
+        assert_eq!(truncate("part_one", 200.0), Some("part_one".into()));
+        assert_eq!(truncate("part_one", 48.0), Some("part..".into()));
+        assert_eq!(truncate("part_one", 20.0), None);
+    }
+}
@@ -0,0 +1,215 @@
+/// Summarises the heap profiles that dhat writes to `dhat-heap.json` when a solution runs with the `dhat-heap` feature.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use tinyjson::JsonValue;
+
+use crate::template::allocations::format_bytes;
+
+pub static DHAT_FILE_NAME: &str = "dhat-heap.json";
+
+/// Number of allocation sites listed in a summary.
+const TOP_SITES: usize = 3;
+
+/// Allocations that happened at the same place in the solution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AllocationSite {
+    /// The frame of the backtrace that allocated, e.g. `_2024_01::part_two (src/bin/2024_01.rs:37:21)`.
+    pub frame: String,
+    pub bytes: u64,
+    pub blocks: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeapSummary {
+    pub total_bytes: u64,
+    pub total_blocks: u64,
+    /// Bytes allocated when the heap was at its largest.
+    pub peak_bytes: u64,
+    pub peak_blocks: u64,
+    /// Sites sorted by the bytes they allocated, largest first.
+    pub sites: Vec<AllocationSite>,
+}
+
+impl HeapSummary {
+    /// Parses the JSON file written by dhat.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let json = JsonValue::from_str(s).or(Err("not a valid JSON file."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected the heap profile to be an object.")?;
+
+        let frames: Vec<&str> = json
+            .get("ftbl")
+            .and_then(|ftbl| ftbl.get::<Vec<JsonValue>>())
+            .ok_or("expected `ftbl` to be an array.")?
+            .iter()
+            .map(|frame| frame.get::<String>().map_or("", String::as_str))
+            .collect();
+
+        let points = json
+            .get("pps")
+            .and_then(|pps| pps.get::<Vec<JsonValue>>())
+            .ok_or("expected `pps` to be an array.")?;
+
+        let mut summary = HeapSummary {
+            total_bytes: 0,
+            total_blocks: 0,
+            peak_bytes: 0,
+            peak_blocks: 0,
+            sites: vec![],
+        };
+        let mut sites: HashMap<String, AllocationSite> = HashMap::new();
+
+        for point in points {
+            let point = point
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("expected a program point to be an object.")?;
+            let number = |key: &str| -> u64 {
+                point
+                    .get(key)
+                    .and_then(|value| value.get::<f64>())
+                    .map_or(0, |&value| value as u64)
+            };
+
+            let (bytes, blocks) = (number("tb"), number("tbk"));
+            summary.total_bytes += bytes;
+            summary.total_blocks += blocks;
+            summary.peak_bytes += number("gb");
+            summary.peak_blocks += number("gbk");
+
+            let indices = point
+                .get("fs")
+                .and_then(|fs| fs.get::<Vec<JsonValue>>())
+                .ok_or("expected `fs` to be an array.")?;
+            let backtrace: Vec<&str> = indices
+                .iter()
+                .filter_map(|index| index.get::<f64>())
+                .filter_map(|&index| frames.get(index as usize).copied())
+                .map(strip_address)
+                .collect();
+
+            let frame = find_site(&backtrace).unwrap_or("[unknown]").to_string();
+            let site = sites
+                .entry(frame.clone())
+                .or_insert_with(|| AllocationSite {
+                    frame,
+                    bytes: 0,
+                    blocks: 0,
+                });
+            site.bytes += bytes;
+            site.blocks += blocks;
+        }
+
+        summary.sites = sites.into_values().collect();
+        summary
+            .sites
+            .sort_unstable_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.frame.cmp(&b.frame)));
+
+        Ok(summary)
+    }
+}
+
+/// Removes the instruction address from a frame, e.g. `0x561efb5df724: `.
+fn strip_address(frame: &str) -> &str {
+    match frame.split_once(": ") {
+        Some((address, rest)) if address.starts_with("0x") => rest,
+        _ => frame,
+    }
+}
+
+/// Returns the innermost frame of a backtrace in a solution, or the innermost frame if none is.
+fn find_site<'a>(backtrace: &[&'a str]) -> Option<&'a str> {
+    backtrace
+        .iter()
+        .find(|frame| frame.contains("src/bin/"))
+        .or_else(|| backtrace.first())
+        .copied()
+}
+
+impl Display for HeapSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Heap: {} in {} allocations, peak {} in {} blocks",
+            format_bytes(self.total_bytes),
+            self.total_blocks,
+            format_bytes(self.peak_bytes),
+            self.peak_blocks
+        )?;
+
+        for site in self.sites.iter().take(TOP_SITES) {
+            write!(
+                f,
+                "\n  ↳ {} in {} allocations at {}",
+                format_bytes(site.bytes),
+                site.blocks,
+                site.frame
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::HeapSummary;
+
+    const PROFILE: &str = include_str!("fixtures/dhat-heap.json");
+
+    #[test]
+    fn parses_totals() {
+        let summary = HeapSummary::parse(PROFILE).unwrap();
+
+        assert_eq!(summary.total_bytes, 1228);
+        assert_eq!(summary.total_blocks, 21);
+        assert_eq!(summary.peak_bytes, 188);
+        assert_eq!(summary.peak_blocks, 4);
+    }
+
+    #[test]
+    fn groups_allocation_sites_by_solution_frame() {
+        let summary = HeapSummary::parse(PROFILE).unwrap();
+        let sites: Vec<(&str, u64)> = summary
+            .sites
+            .iter()
+            .map(|site| (site.frame.as_str(), site.bytes))
+            .collect();
+
+        assert_eq!(
+            sites,
+            vec![
+                ("_2024_01::part_two (src/bin/2024_01.rs:40:5)", 1024),
+                ("_2024_01::part_two (src/bin/2024_01.rs:37:21)", 140),
+                ("_2024_01::parse (src/bin/2024_01.rs:12:9)", 48),
+                ("std::io::stdio::_print (std/src/io/stdio.rs:1233:5)", 16),
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_summary() {
+        let summary = HeapSummary::parse(PROFILE).unwrap().to_string();
+        let lines: Vec<&str> = summary.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0],
+            "Heap: 1.2 KiB in 21 allocations, peak 188 B in 4 blocks"
+        );
+        assert_eq!(
+            lines[1],
+            "  ↳ 1.0 KiB in 16 allocations at _2024_01::part_two (src/bin/2024_01.rs:40:5)"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_profile() {
+        assert!(HeapSummary::parse("[]").is_err());
+        assert!(HeapSummary::parse("{\"pps\": []}").is_err());
+    }
+}
@@ -0,0 +1,316 @@
+/// Append-only history of benchmark runs, stored as one JSON object per line in `data/YYYY/history.jsonl`.
+use std::collections::HashMap;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{Error, Write};
+use std::process::Command;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tinyjson::JsonValue;
+
+use crate::template::timings::{Timing, Timings};
+use crate::template::{get_year_path, Day, Year};
+
+static HISTORY_FILE_NAME: &str = "history.jsonl";
+
+/// A single benchmark run.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// Short hash of the checked out commit, suffixed with `-dirty` if there are uncommitted changes.
+    pub commit: Option<String>,
+    /// Name of the machine the run was measured on.
+    pub machine: String,
+    pub timings: Timings,
+}
+
+/// All benchmark runs of a year, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub entries: Vec<HistoryEntry>,
+}
+
+/// Median timings of a day's parts in a single run. Durations are in nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrendPoint {
+    pub timestamp: u64,
+    pub commit: Option<String>,
+    pub machine: String,
+    pub part_1: Option<f64>,
+    pub part_2: Option<f64>,
+}
+
+impl HistoryEntry {
+    /// Creates an entry for timings measured now, on this machine and commit.
+    pub fn new(timings: Timings) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            commit: current_commit(),
+            machine: machine_id(),
+            timings,
+        }
+    }
+}
+
+impl History {
+    /// Appends a run to the history file of the year. Earlier runs are never rewritten.
+    pub fn append(year: Year, entry: &HistoryEntry) -> Result<(), Error> {
+        let line = JsonValue::from(entry)
+            .stringify()
+            .map_err(|e| Error::other(e.to_string()))?;
+
+        let path = get_year_path(year);
+        fs::create_dir_all(&path)?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path.join(HISTORY_FILE_NAME))?;
+        writeln!(file, "{line}")
+    }
+
+    /// Reads the history of a year. Skips lines that can not be parsed, returns an empty history if not present.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(get_year_path(year).join(HISTORY_FILE_NAME))
+            .map(|s| Self::parse(&s))
+            .unwrap_or_default()
+    }
+
+    fn parse(s: &str) -> Self {
+        let entries = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| JsonValue::from_str(line).ok())
+            .filter_map(|json| HistoryEntry::try_from(&json).ok())
+            .collect();
+
+        History { entries }
+    }
+
+    /// Collects the median timings of a day across all runs that measured it.
+    pub fn trend(&self, day: Day) -> Vec<TrendPoint> {
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                let timing = entry.timings.data.iter().find(|t| t.day == day)?;
+                let median = |part| timing.part_stats(part).map(|s| s.median);
+
+                Some(TrendPoint {
+                    timestamp: entry.timestamp,
+                    commit: entry.commit.clone(),
+                    machine: entry.machine.clone(),
+                    part_1: median(1),
+                    part_2: median(2),
+                })
+                .filter(|point| point.part_1.is_some() || point.part_2.is_some())
+            })
+            .collect()
+    }
+}
+
+/// Returns the short hash of the checked out commit, if the data directory is part of a git repository.
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|output| !output.stdout.is_empty());
+
+    Some(if is_dirty {
+        format!("{hash}-dirty")
+    } else {
+        hash
+    })
+}
+
+/// Identifies the machine, from `AOC_MACHINE` or the host name.
+fn machine_id() -> String {
+    ["AOC_MACHINE", "HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|key| env::var(key).ok())
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".into())
+}
+
+/// Formats seconds since the Unix epoch as a UTC date and time, e.g. `2024-12-01 05:00`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let seconds = timestamp % 86_400;
+
+    // NOTE: converts days since the epoch to a civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        (seconds % 3600) / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("machine".into(), JsonValue::String(value.machine.clone()));
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected entry.timestamp to be a number.")? as u64;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected entry.commit to be null or string.")?
+            .cloned();
+
+        let machine = json
+            .get("machine")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected entry.machine to be a string.")?
+            .clone();
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected entry.data to be an array.")?
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(HistoryEntry {
+            timestamp,
+            commit,
+            machine,
+            timings: Timings { data },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_timestamp, History, HistoryEntry};
+    use crate::day;
+    use crate::template::stats::BenchStats;
+    use crate::template::timings::{Timing, Timings};
+
+    fn stats(median: f64) -> BenchStats {
+        BenchStats {
+            samples: 10,
+            warmup: 1,
+            min: median,
+            median,
+            p95: median,
+            mean: median,
+            std_dev: 0.0,
+            ci_95: 0.0,
+            outliers: 0,
+        }
+    }
+
+    fn entry(timestamp: u64, commit: &str, part_1_median: f64) -> HistoryEntry {
+        HistoryEntry {
+            timestamp,
+            commit: Some(commit.into()),
+            machine: "ferris".into(),
+            timings: Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    part_1: Some("1.0ms".into()),
+                    part_2: None,
+                    part_1_stats: Some(stats(part_1_median)),
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    total_nanos: part_1_median,
+                }],
+            },
+        }
+    }
+
+    #[test]
+    fn round_trips_entries() {
+        let lines: Vec<String> = [entry(1, "abc1234", 2e6), entry(2, "def5678", 1e6)]
+            .iter()
+            .map(|e| tinyjson::JsonValue::from(e).stringify().unwrap())
+            .collect();
+
+        let history = History::parse(&format!("{}\nnot json\n{}\n", lines[0], lines[1]));
+        assert_eq!(history.entries.len(), 2);
+        assert_eq!(history.entries[1].commit.as_deref(), Some("def5678"));
+        assert_eq!(history.entries[1].machine, "ferris");
+    }
+
+    #[test]
+    fn collects_day_trends() {
+        let history = History {
+            entries: vec![entry(1, "abc1234", 2e6), entry(2, "def5678", 1e6)],
+        };
+
+        let trend = history.trend(day!(1));
+        assert_eq!(trend.len(), 2);
+        assert_eq!(trend[0].part_1, Some(2e6));
+        assert_eq!(trend[1].part_1, Some(1e6));
+        assert_eq!(trend[1].part_2, None);
+        assert!(history.trend(day!(2)).is_empty());
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_029_200), "2024-12-01 05:00");
+    }
+}
@@ -0,0 +1,268 @@
+/// Resolves the input of a solution binary from its command-line arguments.
+use std::fmt::Display;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::{env, fs};
+
+use crate::template::examples::{get_example_path, get_expected_path, parse_expected};
+use crate::template::{get_data_path_part, params, Error, PuzzleId, Year};
+
+/// Where a solution binary reads its input from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input in the data directory.
+    Puzzle,
+    /// A file passed with `--input <path>`.
+    File(PathBuf),
+    /// Input piped to stdin, selected with `-` or `--input -`.
+    Stdin(String),
+    /// An example in the data directory, selected with `--example [name]`.
+    Example(Option<String>),
+}
+
+/// The puzzle of an input file, used to suggest how to download it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Download {
+    pub id: PuzzleId,
+    /// The year `cargo download` uses without `--year`.
+    pub default_year: Option<Year>,
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// The file does not exist. Holds the puzzle if the file is its input.
+    Missing(PathBuf, Option<Download>),
+    /// The file is empty. Holds the puzzle if the file is its input.
+    Empty(PathBuf, Option<Download>),
+    IO(PathBuf, io::Error),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (message, path, download) = match self {
+            InputError::Missing(path, download) => ("does not exist", path, download),
+            InputError::Empty(path, download) => ("is empty", path, download),
+            InputError::IO(path, e) => {
+                return write!(f, "could not read input file `{}`: {e}", path.display())
+            }
+        };
+
+        write!(f, "input file `{}` {message}.", path.display())?;
+
+        if let Some(Download { id, default_year }) = download {
+            if *default_year == Some(id.year) {
+                write!(f, " Run `cargo download {}` to download it.", id.day)?;
+            } else {
+                write!(
+                    f,
+                    " Run `cargo download --year {} {}` to download it.",
+                    id.year, id.day
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl InputSource {
+    /// Reads the input source from the command-line arguments of a solution binary.
+    /// Fails if the arguments are invalid or stdin can not be read.
+    pub fn from_args() -> Result<Self, Error> {
+        let args: Vec<String> = env::args().skip(1).collect();
+        Self::parse(&args).map_err(Error::Usage)
+    }
+
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let source = if let Some(index) = args.iter().position(|x| x == "--input") {
+            match args.get(index + 1).map(String::as_str) {
+                Some("-") => Self::read_stdin()?,
+                Some(path) => InputSource::File(PathBuf::from(path)),
+                None => {
+                    return Err(
+                        "Unexpected command-line input. Format: cargo solve 1 --input <path>"
+                            .into(),
+                    )
+                }
+            }
+        } else if let Some(index) = args.iter().position(|x| x == "--example") {
+            // NOTE: the name of the example is optional.
+            let name = args.get(index + 1).filter(|x| !x.starts_with('-')).cloned();
+            InputSource::Example(name)
+        } else if args.iter().any(|x| x == "-") {
+            Self::read_stdin()?
+        } else {
+            InputSource::Puzzle
+        };
+
+        if source != InputSource::Puzzle && args.iter().any(|x| x == "--submit") {
+            return Err("Only answers for the puzzle input can be submitted.".into());
+        }
+
+        Ok(source)
+    }
+
+    fn read_stdin() -> Result<Self, String> {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|e| format!("could not read input from stdin: {e}"))?;
+        Ok(InputSource::Stdin(input))
+    }
+
+    /// Reads the input of a part. `default_year` is the year that `cargo download` resolves
+    /// without `--year`, it decides whether the hint of a missing puzzle input includes it.
+    pub fn read(
+        &self,
+        id: PuzzleId,
+        part: u8,
+        default_year: Option<Year>,
+    ) -> Result<String, InputError> {
+        let (path, puzzle) = match self {
+            InputSource::Stdin(input) => return Ok(input.clone()),
+            InputSource::Puzzle => (
+                get_data_path_part("inputs", id, part),
+                Some(Download { id, default_year }),
+            ),
+            InputSource::File(path) => (path.clone(), None),
+            InputSource::Example(_) => match self.example_name(id) {
+                Some(name) => (get_example_path(id, &name), None),
+                None => (get_data_path_part("examples", id, part), None),
+            },
+        };
+
+        let input = fs::read_to_string(&path).map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => InputError::Missing(path.clone(), puzzle),
+            _ => InputError::IO(path.clone(), e),
+        })?;
+
+        if input.trim().is_empty() {
+            return Err(InputError::Empty(path, puzzle));
+        }
+
+        Ok(input)
+    }
+
+    /// Runs `f` with the parameters of the selected example, if any.
+    /// Fails if the example's expected answers can not be parsed, e.g. because they set an unknown parameter.
+    pub fn with_params<R>(
+        &self,
+        id: PuzzleId,
+        params: &[&str],
+        f: impl FnOnce() -> Result<R, Error>,
+    ) -> Result<R, Error> {
+        let Some(path) = self
+            .example_name(id)
+            .map(|name| get_expected_path(id, &name))
+        else {
+            return f();
+        };
+
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return f(),
+            Err(e) => return Err(Error::IO(format!("could not read {}", path.display()), e)),
+        };
+
+        let expected = parse_expected(&contents, params)
+            .map_err(|e| Error::Parse(format!("could not parse {}: {e}", path.display())))?;
+
+        let overrides: Vec<(&str, &str)> = expected
+            .params
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+
+        params::with_overrides(&overrides, f)
+    }
+
+    /// Returns the name of the selected example. Without a name, uses the day's example file (e.g. `01.txt`)
+    /// if present and the first of the numbered examples (`01-a.txt`) otherwise.
+    fn example_name(&self, id: PuzzleId) -> Option<String> {
+        match self {
+            InputSource::Example(Some(name)) => Some(name.clone()),
+            InputSource::Example(None) if !get_data_path_part("examples", id, 1).exists() => {
+                Some("a".into())
+            }
+            _ => None,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{InputError, InputSource};
+    use crate::{day, template::PuzzleId, year};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(InputSource::parse(&args(&[])), Ok(InputSource::Puzzle));
+        assert_eq!(
+            InputSource::parse(&args(&["--input", "foo.txt"])),
+            Ok(InputSource::File(PathBuf::from("foo.txt")))
+        );
+        assert_eq!(
+            InputSource::parse(&args(&["--example"])),
+            Ok(InputSource::Example(None))
+        );
+        assert_eq!(
+            InputSource::parse(&args(&["--example", "--time"])),
+            Ok(InputSource::Example(None))
+        );
+        assert_eq!(
+            InputSource::parse(&args(&["--example", "b"])),
+            Ok(InputSource::Example(Some("b".into())))
+        );
+    }
+
+    #[test]
+    fn refuses_submitting_other_inputs() {
+        assert!(InputSource::parse(&args(&["--example", "--submit", "1"])).is_err());
+        assert!(InputSource::parse(&args(&["--input", "foo.txt", "--submit", "1"])).is_err());
+        assert!(InputSource::parse(&args(&["--input"])).is_err());
+    }
+
+    #[test]
+    fn reads_example_inputs() {
+        let id = PuzzleId::new(year!(2024), day!(20));
+        let input = InputSource::Example(Some("a".into()))
+            .read(id, 1, None)
+            .unwrap();
+        assert!(input.starts_with("###"));
+    }
+
+    #[test]
+    fn reports_missing_puzzle_inputs() {
+        let id = PuzzleId::new(year!(2015), day!(1));
+        let error = InputSource::Puzzle
+            .read(id, 1, Some(year!(2015)))
+            .unwrap_err();
+        assert!(matches!(error, InputError::Missing(_, Some(_))));
+        assert!(error.to_string().contains("Run `cargo download 01`"));
+
+        let error = InputSource::Puzzle
+            .read(id, 1, Some(year!(2024)))
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Run `cargo download --year 2015 01`"));
+    }
+
+    #[test]
+    fn reports_missing_files() {
+        let id = PuzzleId::new(year!(2024), day!(1));
+        let error = InputSource::File(PathBuf::from("does-not-exist.txt"))
+            .read(id, 1, None)
+            .unwrap_err();
+        assert!(matches!(error, InputError::Missing(_, None)));
+        assert!(!error.to_string().contains("cargo download"));
+    }
+}
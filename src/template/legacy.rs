@@ -0,0 +1,224 @@
+/// Migrates the data layout from before data was split by year: `data/inputs/DD.txt`, `data/examples/DD.txt`,
+/// `data/puzzles/DD.md` and `data/timings.json`. The data belongs to the year the project was set up for, the
+/// default year `AOC_YEAR`, and is moved to `data/YYYY/` the first time a command runs.
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use tinyjson::JsonValue;
+
+use crate::template::timings::{Timings, TIMINGS_FILE_NAME};
+use crate::template::{Error, Year};
+
+const LEGACY_FOLDERS: [&str; 3] = ["inputs", "examples", "puzzles"];
+
+/// Moves the legacy data of the project into the default year. Prints the moved files.
+pub fn migrate() -> Result<(), Error> {
+    let Some(year) = Year::from_env() else {
+        return Ok(());
+    };
+
+    let moved = migrate_in(Path::new("data"), year)
+        .map_err(|e| Error::IO("failed to migrate the legacy data layout".into(), e))?;
+
+    for (from, to) in moved {
+        eprintln!("Moved \"{}\" to \"{}\"", from.display(), to.display());
+    }
+
+    Ok(())
+}
+
+/// Moves the legacy data in `data` into the year's folder. Files that exist in both are kept in place.
+/// Returns the pairs of moved paths.
+fn migrate_in(data: &Path, year: Year) -> Result<Vec<(PathBuf, PathBuf)>, io::Error> {
+    let year_path = data.join(year.to_string());
+    let mut moved = vec![];
+
+    for folder in LEGACY_FOLDERS {
+        let legacy = data.join(folder);
+        let entries = match fs::read_dir(&legacy) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+
+        for entry in entries {
+            let entry = entry?;
+            if entry.file_name() == ".keep" || !entry.file_type()?.is_file() {
+                continue;
+            }
+
+            let to = year_path.join(folder).join(entry.file_name());
+            if to.exists() {
+                eprintln!(
+                    "Warning: kept \"{}\", \"{}\" already exists.",
+                    entry.path().display(),
+                    to.display()
+                );
+                continue;
+            }
+
+            fs::create_dir_all(year_path.join(folder))?;
+            fs::rename(entry.path(), &to)?;
+            moved.push((entry.path(), to));
+        }
+
+        remove_if_empty(&legacy)?;
+    }
+
+    let legacy_timings = data.join(TIMINGS_FILE_NAME);
+    if legacy_timings.exists() {
+        let to = year_path.join(TIMINGS_FILE_NAME);
+        if migrate_timings(&legacy_timings, &to)? {
+            moved.push((legacy_timings, to));
+        }
+    }
+
+    Ok(moved)
+}
+
+/// Merges the legacy timings into the year's timings, which are newer and take precedence. Returns whether the
+/// timings were migrated. Timings that can not be parsed are kept in place.
+fn migrate_timings(legacy: &Path, to: &Path) -> Result<bool, io::Error> {
+    let read = |path: &Path| match fs::read_to_string(path) {
+        Ok(contents) => Ok(Timings::try_from(contents)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(Ok(Timings::default())),
+        Err(e) => Err(e),
+    };
+
+    let timings = match (read(legacy)?, read(to)?) {
+        (Ok(legacy), Ok(current)) => legacy.merge(&current),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!(
+                "Warning: kept \"{}\", the timings could not be migrated: {e}",
+                legacy.display()
+            );
+            return Ok(false);
+        }
+    };
+
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    JsonValue::from(timings).format_to(&mut fs::File::create(to)?)?;
+
+    // NOTE: the legacy file is only removed once its timings were written to the year's file.
+    fs::remove_file(legacy)?;
+    Ok(true)
+}
+
+/// Removes a legacy folder that has no files left besides its `.keep`.
+fn remove_if_empty(path: &Path) -> Result<(), io::Error> {
+    let is_empty = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.file_name() == ".keep"))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .all(|is_keep| is_keep);
+
+    if is_empty {
+        match fs::remove_file(path.join(".keep")) {
+            Err(e) if e.kind() != ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+        fs::remove_dir(path)?;
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+    use std::{env, fs, process};
+
+    use super::migrate_in;
+    use crate::template::timings::Timings;
+    use crate::year;
+
+    /// Creates a temporary data folder in the legacy layout.
+    fn legacy_data(name: &str) -> PathBuf {
+        let data = env::temp_dir().join(format!("aoc-legacy-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&data);
+        for folder in ["inputs", "examples", "puzzles"] {
+            fs::create_dir_all(data.join(folder)).unwrap();
+            fs::write(data.join(folder).join(".keep"), "").unwrap();
+        }
+        data
+    }
+
+    fn timings_json(day: u8, nanos: f64) -> String {
+        format!(
+            r#"{{"data":[{{"day":"{day:02}","part_1":"1ms","part_2":null,"total_nanos":{nanos}}}]}}"#
+        )
+    }
+
+    #[test]
+    fn moves_legacy_data_into_the_year() {
+        let data = legacy_data("moves");
+        fs::write(data.join("inputs").join("01.txt"), "1").unwrap();
+        fs::write(data.join("puzzles").join("01.md"), "# Day 1").unwrap();
+        fs::write(data.join("timings.json"), timings_json(1, 1e6)).unwrap();
+
+        let moved = migrate_in(&data, year!(2023)).unwrap();
+        assert_eq!(moved.len(), 3);
+
+        let year = data.join("2023");
+        assert_eq!(
+            fs::read_to_string(year.join("inputs").join("01.txt")).unwrap(),
+            "1"
+        );
+        assert!(year.join("puzzles").join("01.md").exists());
+        assert!(!data.join("inputs").exists());
+        assert!(!data.join("examples").exists());
+        assert!(!data.join("timings.json").exists());
+
+        let timings =
+            Timings::try_from(fs::read_to_string(year.join("timings.json")).unwrap()).unwrap();
+        assert_eq!(timings.data.len(), 1);
+    }
+
+    #[test]
+    fn keeps_conflicting_files() {
+        let data = legacy_data("conflicts");
+        fs::write(data.join("inputs").join("01.txt"), "old").unwrap();
+        fs::create_dir_all(data.join("2023").join("inputs")).unwrap();
+        fs::write(data.join("2023").join("inputs").join("01.txt"), "new").unwrap();
+
+        assert!(migrate_in(&data, year!(2023)).unwrap().is_empty());
+        assert_eq!(
+            fs::read_to_string(data.join("inputs").join("01.txt")).unwrap(),
+            "old"
+        );
+        assert_eq!(
+            fs::read_to_string(data.join("2023").join("inputs").join("01.txt")).unwrap(),
+            "new"
+        );
+    }
+
+    #[test]
+    fn merges_legacy_timings() {
+        let data = legacy_data("timings");
+        fs::write(data.join("timings.json"), timings_json(1, 1e6)).unwrap();
+        fs::create_dir_all(data.join("2023")).unwrap();
+        fs::write(data.join("2023").join("timings.json"), timings_json(2, 2e6)).unwrap();
+
+        migrate_in(&data, year!(2023)).unwrap();
+
+        let timings =
+            Timings::try_from(fs::read_to_string(data.join("2023").join("timings.json")).unwrap())
+                .unwrap();
+        assert_eq!(timings.data.len(), 2);
+        assert!(!data.join("timings.json").exists());
+    }
+
+    #[test]
+    fn keeps_invalid_legacy_timings() {
+        let data = legacy_data("invalid");
+        fs::write(data.join("timings.json"), "{").unwrap();
+
+        assert!(migrate_in(&data, year!(2023)).unwrap().is_empty());
+        assert!(data.join("timings.json").exists());
+        assert!(!data.join("2023").join("timings.json").exists());
+    }
+}
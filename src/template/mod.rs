@@ -1,42 +1,95 @@
-use std::{env, fs};
+use std::{env, fs, path::PathBuf};
 
+pub mod allocations;
 pub mod aoc_cli;
+pub mod aoc_client;
+pub mod cli;
 pub mod commands;
+pub mod config;
+pub mod examples;
+pub mod input;
+pub mod legacy;
+pub mod output;
+pub mod params;
 pub mod runner;
 
 pub use day::*;
+pub use error::Error;
+pub use year::*;
 
+mod answers;
 mod day;
+mod error;
+mod flamegraph;
+mod heap_profile;
+mod history;
+mod plan;
+mod puzzle;
 mod readme_benchmarks;
+mod readme_stars;
 mod run_multi;
+mod stats;
+mod submissions;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that returns the data directory of a year, e.g. `data/2024`.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn get_year_path(year: Year) -> PathBuf {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
+    cwd.join("data").join(year.to_string())
+}
+
+/// Helper function that returns the path of a day's data file, e.g. `data/2024/inputs/01.txt`.
+#[must_use]
+pub fn get_data_path(folder: &str, id: PuzzleId) -> PathBuf {
+    get_year_path(id.year)
+        .join(folder)
+        .join(format!("{}.txt", id.day))
+}
+
+/// Helper function that returns the path of a part's data file, e.g. `data/2024/inputs/01-2.txt`.
+/// Falls back to the day's data file if the part does not have a file of its own.
+#[must_use]
+pub fn get_data_path_part(folder: &str, id: PuzzleId, part: u8) -> PathBuf {
+    let path = get_year_path(id.year)
+        .join(folder)
+        .join(format!("{}-{part}.txt", id.day));
+
+    if path.exists() {
+        path
+    } else {
+        get_data_path(folder, id)
+    }
+}
+
+/// Helper function that reads a text file to a string.
+#[must_use]
+pub fn read_file(folder: &str, id: PuzzleId) -> String {
+    let f = fs::read_to_string(get_data_path(folder, id));
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+/// Falls back to the day's file, e.g. `01.txt`, if the part does not have a file of its own.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
+pub fn read_file_part(folder: &str, id: PuzzleId, part: u8) -> String {
+    let f = fs::read_to_string(get_data_path_part(folder, id, part));
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE` and sets up the input and runner for each part.
+/// Also registers the solution as `SOLUTION`, which is picked up by the in-process multi-day runner.
+///
+/// The year is read from the name of the solution file, which must follow the scheme `src/bin/YYYY_DD.rs`.
+/// A part reads its own input file (e.g. `01-2.txt`) if present, and the day's input otherwise.
+/// The binary accepts `--input <path>`, `--example [name]` and `-` (stdin) to run other inputs,
+/// and `--part <1 | 2>` to run a single part.
+/// Parts return `Option<T>`, or `Result<T, E>` to report errors such as malformed input.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
 #[macro_export]
@@ -52,17 +105,70 @@ macro_rules! solution {
     };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        /// The year of the current puzzle.
+        const YEAR: $crate::template::Year = $crate::template::Year::__from_bin_path(file!());
+
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The current puzzle.
+        const PUZZLE: $crate::template::PuzzleId = $crate::template::PuzzleId::new(YEAR, DAY);
+
+        /// The solution entry for the in-process multi-day runner.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::runner::Solution = $crate::template::runner::Solution {
+            year: YEAR,
+            day: DAY,
+            parts: &[
+                $( ($part, |input, options| {
+                    $crate::template::runner::execute_part($func, input, PUZZLE, $part, options)
+                }), )*
+            ],
+        };
+
+        // NOTE: solutions without parameters fall back to the empty list, `params!` shadows this glob import.
+        #[allow(unused_imports)]
+        use __default_params::*;
+
+        mod __default_params {
+            /// The names of the puzzle's parameters, declared with `params!`.
+            pub const PARAM_NAMES: &[&str] = &[];
+        }
+
+        /// The parts of the solution, returning their answer as a string. Used to check examples.
+        #[allow(dead_code)]
+        const PARTS: &[$crate::template::examples::ExamplePart] = &[
+            $( ($part, |input| $crate::template::runner::Answer::to_answer(&$func(input))), )*
+        ];
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let run = || -> Result<(), $crate::template::Error> {
+                let source = $crate::template::input::InputSource::from_args()?;
+                let options = RunOptions::from_args()?;
+                // NOTE: an invalid `aoc.toml` only affects the download hint of a missing input.
+                let default_year = $crate::template::config::Config::load().unwrap_or_default().default_year();
+                let results = source.with_params(PUZZLE, PARAM_NAMES, || {
+                    let mut results = vec![];
+                    $(
+                        if options.runs_part($part) {
+                            results.push(run_part($func, &source.read(PUZZLE, $part, default_year)?, PUZZLE, $part)?);
+                        }
+                    )*
+                    Ok(results)
+                })?;
+                $crate::template::output::emit_summary(&results, options.format);
+                Ok(())
+            };
+
+            if let Err(e) = run() {
+                eprintln!("Error: {e}");
+                std::process::exit(e.exit_code());
+            }
         }
     };
 }
@@ -0,0 +1,317 @@
+/// Machine-readable output of solution results.
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::{stdout, Write};
+use std::str::FromStr;
+
+use tinyjson::JsonValue;
+
+use crate::template::runner::PartResult;
+
+/// Controls how results are written to stdout.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable, ANSI-decorated text.
+    #[default]
+    Text,
+    /// A single JSON array of all records, written once all parts have run.
+    Json,
+    /// One JSON record per line, written as soon as a part has run.
+    Ndjson,
+}
+
+impl OutputFormat {
+    pub fn is_text(self) -> bool {
+        self == OutputFormat::Text
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Json => "json",
+            OutputFormat::Ndjson => "ndjson",
+        })
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl std::error::Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `text`, `json` or `ndjson`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The outcome of a single part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The part returned an answer.
+    Solved,
+    /// The part ran, but returned no answer.
+    NoAnswer,
+    /// The part returned an error.
+    Failed,
+    /// The part panicked.
+    Crashed,
+    /// The part did not finish within the timeout.
+    TimedOut,
+    /// The day has not been scaffolded yet.
+    NotImplemented,
+    /// The input file for the day is missing.
+    MissingInput,
+}
+
+impl Status {
+    /// Whether the part failed, crashed or timed out.
+    pub fn is_failure(self) -> bool {
+        matches!(self, Status::Failed | Status::Crashed | Status::TimedOut)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::NoAnswer => "no_answer",
+            Status::Failed => "failed",
+            Status::Crashed => "crashed",
+            Status::TimedOut => "timed_out",
+            Status::NotImplemented => "not_implemented",
+            Status::MissingInput => "missing_input",
+        }
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+thread_local! {
+    static CAPTURED: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Writes text to stdout, or to the buffer of the current thread while its output is [captured](capture).
+pub fn write_text(text: &str) {
+    let is_captured = CAPTURED.with(|captured| match captured.borrow_mut().as_mut() {
+        Some(buffer) => {
+            buffer.push_str(text);
+            true
+        }
+        None => false,
+    });
+
+    if !is_captured {
+        print!("{text}");
+        let _ = stdout().flush();
+    }
+}
+
+/// Whether the output of the current thread is captured.
+pub fn is_capturing() -> bool {
+    CAPTURED.with(|captured| captured.borrow().is_some())
+}
+
+/// Runs `f` and returns its result along with the text it wrote with [`write_text`] on the current thread.
+pub fn capture<R>(f: impl FnOnce() -> R) -> (R, String) {
+    let previous = CAPTURED.with(|captured| captured.replace(Some(String::new())));
+    let result = f();
+    let text = CAPTURED.with(|captured| captured.replace(previous));
+    (result, text.unwrap_or_default())
+}
+
+/// Writes a record as a single line if streaming output was requested.
+pub fn emit_record(result: &PartResult, format: OutputFormat) {
+    if format == OutputFormat::Ndjson {
+        if let Ok(line) = JsonValue::from(result).stringify() {
+            write_text(&format!("{line}\n"));
+        }
+    }
+}
+
+/// Writes all records as a JSON array if JSON output was requested.
+pub fn emit_summary(results: &[PartResult], format: OutputFormat) {
+    if format == OutputFormat::Json {
+        let json = JsonValue::Array(results.iter().map(JsonValue::from).collect());
+        if let Ok(str) = json.format() {
+            println!("{str}");
+        }
+    }
+}
+
+impl From<&PartResult> for JsonValue {
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "year".into(),
+            JsonValue::Number(f64::from(value.year.into_inner())),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::Number(f64::from(value.day.into_inner())),
+        );
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.duration.as_nanos() as f64),
+        );
+        map.insert("samples".into(), JsonValue::Number(value.samples() as f64));
+        map.insert("status".into(), JsonValue::String(value.status.to_string()));
+        map.insert(
+            "error".into(),
+            value
+                .error
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "allocations".into(),
+            value
+                .allocations
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{capture, is_capturing, write_text, OutputFormat, Status};
+    use crate::template::allocations::AllocStats;
+    use crate::{day, template::runner::PartResult, year};
+
+    #[test]
+    fn parses_output_formats() {
+        assert_eq!("text".parse::<OutputFormat>().unwrap(), OutputFormat::Text);
+        assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!(
+            "ndjson".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Ndjson
+        );
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn captures_output() {
+        let (value, text) = capture(|| {
+            write_text("Day 01\n");
+            let ((), inner) = capture(|| write_text("nested"));
+            assert_eq!(inner, "nested");
+            assert!(is_capturing());
+            42
+        });
+        assert_eq!(value, 42);
+        assert_eq!(text, "Day 01\n");
+        assert!(!is_capturing());
+    }
+
+    #[test]
+    fn serializes_records() {
+        let result = PartResult {
+            year: year!(2024),
+            day: day!(3),
+            part: 2,
+            answer: Some("42".into()),
+            duration: Duration::from_micros(5),
+            stats: None,
+            allocations: Some(AllocStats {
+                allocations: 3,
+                bytes: 96,
+                peak_bytes: 64,
+            }),
+            status: Status::Solved,
+            error: None,
+        };
+
+        let json = JsonValue::from(&result);
+        let map = json.get::<HashMap<String, JsonValue>>().unwrap();
+
+        assert_eq!(map.get("year").unwrap().get::<f64>(), Some(&2024.0));
+        assert_eq!(map.get("day").unwrap().get::<f64>(), Some(&3.0));
+        assert_eq!(map.get("part").unwrap().get::<f64>(), Some(&2.0));
+        assert_eq!(
+            map.get("answer").unwrap().get::<String>(),
+            Some(&"42".to_string())
+        );
+        assert_eq!(
+            map.get("duration_nanos").unwrap().get::<f64>(),
+            Some(&5000.0)
+        );
+        assert_eq!(map.get("samples").unwrap().get::<f64>(), Some(&1.0));
+        assert_eq!(
+            map.get("status").unwrap().get::<String>(),
+            Some(&"solved".to_string())
+        );
+        assert!(map.get("stats").unwrap().is_null());
+        assert!(map.get("error").unwrap().is_null());
+
+        let allocations = map
+            .get("allocations")
+            .unwrap()
+            .get::<HashMap<String, JsonValue>>()
+            .unwrap();
+        assert_eq!(
+            allocations.get("allocations").unwrap().get::<f64>(),
+            Some(&3.0)
+        );
+        assert_eq!(
+            allocations.get("peak_bytes").unwrap().get::<f64>(),
+            Some(&64.0)
+        );
+
+        let result = PartResult {
+            allocations: None,
+            ..result
+        };
+        let json = JsonValue::from(&result);
+        let map = json.get::<HashMap<String, JsonValue>>().unwrap();
+        assert!(map.get("allocations").unwrap().is_null());
+    }
+}
@@ -0,0 +1,120 @@
+/// Named puzzle parameters, e.g. the size of a grid, that differ between examples and real inputs.
+///
+/// A solution declares its parameters with their real-input defaults using [`params!`](crate::params).
+/// Examples override them in their expected answers file (e.g. `grid_size = 7`), tests with [`with_overrides`].
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+thread_local! {
+    static OVERRIDES: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+}
+
+/// Returns the value of a parameter, or the passed default if it is not overridden on the current thread.
+pub fn get<T: FromStr>(name: &str, default: T) -> T {
+    OVERRIDES.with(|overrides| match overrides.borrow().get(name) {
+        Some(value) => value
+            .parse()
+            .unwrap_or_else(|_| panic!("could not parse value `{value}` of parameter `{name}`.")),
+        None => default,
+    })
+}
+
+/// Runs `f` with the passed parameters overridden on the current thread.
+pub fn with_overrides<R>(overrides: &[(&str, &str)], f: impl FnOnce() -> R) -> R {
+    let previous = OVERRIDES.with(|current| {
+        let mut current = current.borrow_mut();
+        let previous = current.clone();
+        for (name, value) in overrides {
+            current.insert((*name).to_string(), (*value).to_string());
+        }
+        previous
+    });
+
+    // NOTE: restore the previous values even if `f` panics.
+    let _guard = RestoreGuard(Some(previous));
+    f()
+}
+
+struct RestoreGuard(Option<HashMap<String, String>>);
+
+impl Drop for RestoreGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0.take() {
+            OVERRIDES.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+}
+
+/// Declares the parameters of a puzzle with their defaults for the real input.
+/// Creates a `Params` struct, read the current values with `Params::get()`, and the `PARAM_NAMES` that examples may set.
+///
+/// ```ignore
+/// advent_of_code::params! {
+///     /// Width and height of the grid.
+///     grid_size: usize = 71,
+/// }
+///
+/// pub fn part_one(input: &str) -> Option<usize> {
+///     let Params { grid_size } = Params::get();
+///     // ...
+/// }
+/// ```
+#[macro_export]
+macro_rules! params {
+    ($( $(#[$meta:meta])* $name:ident: $ty:ty = $default:expr ),+ $(,)?) => {
+        /// The names of the parameters, which examples may override.
+        const PARAM_NAMES: &[&str] = &[$( stringify!($name) ),+];
+
+        /// Parameters of the puzzle, with defaults for the real input.
+        #[derive(Clone, Debug)]
+        struct Params {
+            $( $(#[$meta])* $name: $ty, )+
+        }
+
+        impl Params {
+            /// Returns the parameters, taking overrides of examples and tests into account.
+            fn get() -> Self {
+                Self {
+                    $( $name: $crate::template::params::get(stringify!($name), $default), )+
+                }
+            }
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{get, with_overrides};
+
+    #[test]
+    fn returns_defaults() {
+        assert_eq!(get("grid_size", 71_usize), 71);
+    }
+
+    #[test]
+    fn returns_overrides() {
+        let value = with_overrides(&[("grid_size", "7")], || get("grid_size", 71_usize));
+        assert_eq!(value, 7);
+        assert_eq!(get("grid_size", 71_usize), 71);
+    }
+
+    #[test]
+    fn nests_overrides() {
+        with_overrides(&[("a", "1"), ("b", "2")], || {
+            with_overrides(&[("b", "3")], || {
+                assert_eq!(get("a", 0), 1);
+                assert_eq!(get("b", 0), 3);
+            });
+            assert_eq!(get("b", 0), 2);
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_overrides() {
+        with_overrides(&[("grid_size", "seven")], || get("grid_size", 71_usize));
+    }
+}
@@ -0,0 +1,372 @@
+/// Planned changes to files and directories, applied all at once. Files with content are backed up before they
+/// are replaced, and every change is rolled back if one of them fails.
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// The directory is missing and will be created.
+    CreateDir,
+    /// The file is missing and will be created.
+    Create,
+    /// The file has no content and will be written.
+    Fill,
+    /// The file has content, which will be backed up to the path before it is replaced.
+    Replace(PathBuf),
+    /// The file has content and is kept.
+    Keep,
+}
+
+#[derive(Clone, Debug)]
+pub struct FileChange {
+    /// Describes the file or directory in messages, e.g. `input file`.
+    pub label: &'static str,
+    pub path: PathBuf,
+    pub action: Action,
+    pub contents: String,
+    /// Contents of the file when the change was planned, to restore them on rollback.
+    previous: Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Plan {
+    pub changes: Vec<FileChange>,
+}
+
+#[derive(Debug)]
+pub struct PlanError {
+    pub path: PathBuf,
+    pub error: io::Error,
+    /// Files that could not be restored while rolling back.
+    pub not_restored: Vec<PathBuf>,
+}
+
+impl Display for PlanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "could not write \"{}\": {}.",
+            self.path.display(),
+            self.error
+        )?;
+
+        if self.not_restored.is_empty() {
+            write!(f, " All changes were rolled back.")
+        } else {
+            let paths: Vec<String> = self
+                .not_restored
+                .iter()
+                .map(|path| format!("\"{}\"", path.display()))
+                .collect();
+            write!(f, " Could not roll back {}.", paths.join(", "))
+        }
+    }
+}
+
+impl std::error::Error for PlanError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl Plan {
+    /// Plans to write a file. Files without content are written, files with content are kept unless `overwrite`
+    /// is set. `is_blank` decides whether the existing contents count as content.
+    pub fn add(
+        &mut self,
+        label: &'static str,
+        path: impl Into<PathBuf>,
+        contents: String,
+        overwrite: bool,
+        is_blank: fn(&str) -> bool,
+    ) {
+        let path = path.into();
+        let previous = fs::read_to_string(&path).ok();
+
+        let action = match &previous {
+            None if !path.exists() => Action::Create,
+            None => Action::Keep,
+            Some(s) if *s == contents => Action::Keep,
+            Some(s) if is_blank(s) => Action::Fill,
+            Some(_) if overwrite => Action::Replace(backup_path(&path)),
+            Some(_) => Action::Keep,
+        };
+
+        self.changes.push(FileChange {
+            label,
+            path,
+            action,
+            contents,
+            previous,
+        });
+    }
+
+    /// Plans to create a directory and its missing parents, outermost first. Directories that exist or are
+    /// already planned are skipped.
+    pub fn add_dir(&mut self, label: &'static str, path: impl Into<PathBuf>) {
+        let path = path.into();
+        let missing: Vec<&Path> = path
+            .ancestors()
+            .take_while(|dir| !dir.as_os_str().is_empty() && !dir.exists())
+            .collect();
+
+        for dir in missing.into_iter().rev() {
+            if self.changes.iter().any(|change| change.path == dir) {
+                continue;
+            }
+
+            self.changes.push(FileChange {
+                label,
+                path: dir.to_path_buf(),
+                action: Action::CreateDir,
+                contents: String::new(),
+                previous: None,
+            });
+        }
+    }
+
+    /// Describes the changes, e.g. `Created input file "data/2024/inputs/01.txt"`.
+    pub fn describe(&self, dry_run: bool) -> Vec<String> {
+        self.changes
+            .iter()
+            .map(|change| {
+                let path = change.path.display();
+                let label = change.label;
+
+                match (&change.action, dry_run) {
+                    (Action::CreateDir, false) => format!("Created {label} \"{path}\""),
+                    (Action::CreateDir, true) => format!("Would create {label} \"{path}\""),
+                    (Action::Create, false) => format!("Created {label} \"{path}\""),
+                    (Action::Create, true) => format!("Would create {label} \"{path}\""),
+                    (Action::Fill, false) => format!("Wrote {label} \"{path}\""),
+                    (Action::Fill, true) => format!("Would write {label} \"{path}\""),
+                    (Action::Replace(backup), false) => format!(
+                        "Replaced {label} \"{path}\", backed up to \"{}\"",
+                        backup.display()
+                    ),
+                    (Action::Replace(backup), true) => format!(
+                        "Would replace {label} \"{path}\", backing up to \"{}\"",
+                        backup.display()
+                    ),
+                    (Action::Keep, false) => format!("Kept {label} \"{path}\""),
+                    (Action::Keep, true) => format!("Would keep {label} \"{path}\""),
+                }
+            })
+            .collect()
+    }
+
+    /// Applies all changes in order. If a change fails, it is rolled back with the changes applied before it.
+    pub fn apply(&self) -> Result<(), PlanError> {
+        for (i, change) in self.changes.iter().enumerate() {
+            if let Err(error) = apply_change(change) {
+                return Err(PlanError {
+                    path: change.path.clone(),
+                    error,
+                    // NOTE: the failing change may be partially applied, e.g. a created file without contents.
+                    not_restored: rollback(&self.changes[..=i]),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn apply_change(change: &FileChange) -> Result<(), io::Error> {
+    match &change.action {
+        Action::CreateDir => fs::create_dir(&change.path),
+        Action::Create => OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&change.path)?
+            .write_all(change.contents.as_bytes()),
+        Action::Fill => fs::write(&change.path, &change.contents),
+        Action::Replace(backup) => {
+            fs::copy(&change.path, backup)?;
+            fs::write(&change.path, &change.contents)
+        }
+        Action::Keep => Ok(()),
+    }
+}
+
+/// Reverts changes, newest first. Returns the files that could not be restored. A missing file or backup means
+/// that the change was not applied, which counts as restored.
+fn rollback(changes: &[FileChange]) -> Vec<PathBuf> {
+    changes
+        .iter()
+        .rev()
+        .filter(|change| {
+            let restored = match (&change.action, &change.previous) {
+                (Action::CreateDir, _) => fs::remove_dir(&change.path),
+                (Action::Create, _) => fs::remove_file(&change.path),
+                (Action::Fill, Some(previous)) => fs::write(&change.path, previous),
+                (Action::Replace(backup), _) => fs::rename(backup, &change.path),
+                _ => Ok(()),
+            };
+            restored.is_err_and(|e| e.kind() != io::ErrorKind::NotFound)
+        })
+        .map(|change| change.path.clone())
+        .collect()
+}
+
+/// Returns a free backup path next to the file, e.g. `01.txt.bak` or `01.txt.bak.2`.
+fn backup_path(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+    (1..)
+        .map(|i| match i {
+            1 => path.with_file_name(format!("{name}.bak")),
+            i => path.with_file_name(format!("{name}.bak.{i}")),
+        })
+        .find(|backup| !backup.exists())
+        .unwrap_or_else(|| path.with_file_name(format!("{name}.bak")))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+    use std::{env, fs, process};
+
+    use super::{rollback, Action, Plan};
+
+    /// Creates an empty temporary directory for a test.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-plan-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn is_empty(s: &str) -> bool {
+        s.trim().is_empty()
+    }
+
+    #[test]
+    fn plans_actions() {
+        let dir = test_dir("plans_actions");
+        fs::write(dir.join("blank.txt"), "\n").unwrap();
+        fs::write(dir.join("full.txt"), "data").unwrap();
+
+        let mut plan = Plan::default();
+        plan.add("file", dir.join("missing.txt"), "a".into(), false, is_empty);
+        plan.add("file", dir.join("blank.txt"), "a".into(), false, is_empty);
+        plan.add("file", dir.join("full.txt"), "a".into(), false, is_empty);
+        plan.add("file", dir.join("full.txt"), "data".into(), true, is_empty);
+        plan.add("file", dir.join("full.txt"), "a".into(), true, is_empty);
+
+        let actions: Vec<Action> = plan.changes.into_iter().map(|c| c.action).collect();
+        assert_eq!(
+            actions,
+            vec![
+                Action::Create,
+                Action::Fill,
+                Action::Keep,
+                Action::Keep,
+                Action::Replace(dir.join("full.txt.bak"))
+            ]
+        );
+    }
+
+    #[test]
+    fn applies_changes_with_backups() {
+        let dir = test_dir("applies_changes");
+        fs::write(dir.join("full.txt"), "data").unwrap();
+
+        let mut plan = Plan::default();
+        plan.add("file", dir.join("new.txt"), "a".into(), false, is_empty);
+        plan.add("file", dir.join("full.txt"), "b".into(), true, is_empty);
+        plan.apply().unwrap();
+
+        assert_eq!(fs::read_to_string(dir.join("new.txt")).unwrap(), "a");
+        assert_eq!(fs::read_to_string(dir.join("full.txt")).unwrap(), "b");
+        assert_eq!(
+            fs::read_to_string(dir.join("full.txt.bak")).unwrap(),
+            "data"
+        );
+    }
+
+    #[test]
+    fn creates_missing_directories() {
+        let dir = test_dir("creates_dirs");
+
+        let mut plan = Plan::default();
+        plan.add_dir("directory", dir.join("a").join("b"));
+        plan.add_dir("directory", dir.join("a").join("c"));
+        plan.add_dir("directory", &dir);
+
+        let paths: Vec<PathBuf> = plan.changes.iter().map(|c| c.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                dir.join("a"),
+                dir.join("a").join("b"),
+                dir.join("a").join("c")
+            ]
+        );
+
+        plan.apply().unwrap();
+        assert!(dir.join("a").join("c").is_dir());
+    }
+
+    #[test]
+    fn rolls_back_on_failure() {
+        let dir = test_dir("rolls_back");
+        fs::write(dir.join("full.txt"), "data").unwrap();
+        fs::write(dir.join("blank.txt"), "# comment\n").unwrap();
+
+        let mut plan = Plan::default();
+        plan.add_dir("directory", dir.join("new"));
+        plan.add(
+            "file",
+            dir.join("new").join("new.txt"),
+            "a".into(),
+            false,
+            is_empty,
+        );
+        plan.add("file", dir.join("full.txt"), "b".into(), true, is_empty);
+        plan.add("file", dir.join("blank.txt"), "c".into(), false, |s| {
+            s.starts_with('#')
+        });
+        plan.add(
+            "file",
+            dir.join("missing").join("x.txt"),
+            "d".into(),
+            false,
+            is_empty,
+        );
+
+        let error = plan.apply().unwrap_err();
+        assert_eq!(error.path, dir.join("missing").join("x.txt"));
+        assert!(error.not_restored.is_empty());
+
+        assert!(!dir.join("new").exists());
+        assert!(!dir.join("full.txt.bak").exists());
+        assert_eq!(fs::read_to_string(dir.join("full.txt")).unwrap(), "data");
+        assert_eq!(
+            fs::read_to_string(dir.join("blank.txt")).unwrap(),
+            "# comment\n"
+        );
+    }
+
+    #[test]
+    fn rolls_back_partially_applied_changes() {
+        let dir = test_dir("rolls_back_partially");
+        fs::write(dir.join("full.txt"), "data").unwrap();
+
+        let mut plan = Plan::default();
+        plan.add("file", dir.join("full.txt"), "b".into(), true, is_empty);
+        plan.add("file", dir.join("new.txt"), "a".into(), false, is_empty);
+
+        // NOTE: the file was backed up, but replacing it failed halfway. The new file was never created.
+        fs::copy(dir.join("full.txt"), dir.join("full.txt.bak")).unwrap();
+        fs::write(dir.join("full.txt"), "").unwrap();
+
+        assert!(rollback(&plan.changes).is_empty());
+        assert_eq!(fs::read_to_string(dir.join("full.txt")).unwrap(), "data");
+        assert!(!dir.join("full.txt.bak").exists());
+    }
+}
@@ -0,0 +1,192 @@
+/// Reads the title and examples from a puzzle description, as downloaded to `data/YYYY/puzzles/DD.md`.
+///
+/// Examples are the code blocks that follow a paragraph mentioning an example. Their expected answers are
+/// the last highlighted code span (e.g. `*11*` in a code span) of each part, as highlighted on the website.
+use std::fs;
+
+use crate::template::aoc_cli::get_puzzle_path;
+use crate::template::PuzzleId;
+
+const PART_TWO_HEADING: &str = "--- Part Two ---";
+
+/// An example found in a puzzle description.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PuzzleExample {
+    pub input: String,
+    /// Pairs of part and expected answer.
+    pub answers: Vec<(u8, String)>,
+}
+
+/// Reads the downloaded description of a puzzle, if present.
+pub fn read_markdown(id: PuzzleId) -> Option<String> {
+    fs::read_to_string(get_puzzle_path(id))
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+}
+
+/// Returns the title of the puzzle, e.g. `Historian Hysteria` for `--- Day 1: Historian Hysteria ---`.
+pub fn title(markdown: &str) -> Option<String> {
+    markdown
+        .lines()
+        .map(|line| line.trim_start_matches('#').trim())
+        .find_map(|line| line.strip_prefix("--- Day "))
+        .and_then(|line| line.split_once(':'))
+        .map(|(_, title)| title.trim_end_matches('-').trim().to_string())
+        .filter(|title| !title.is_empty())
+}
+
+/// Finds the examples of both parts. The second part only gets an example of its own if it introduces a new one,
+/// otherwise its answer is added to the example of the first part.
+pub fn extract_examples(markdown: &str) -> Vec<PuzzleExample> {
+    let (part_one, part_two) = match markdown.find(PART_TWO_HEADING) {
+        Some(index) => markdown.split_at(index),
+        None => (markdown, ""),
+    };
+
+    let mut examples: Vec<PuzzleExample> = vec![];
+
+    for (part, section) in [(1, part_one), (2, part_two)] {
+        let blocks = parse_blocks(section);
+        let answer = last_highlight(&blocks);
+
+        match (find_example(&blocks), examples.last_mut()) {
+            (Some(input), _) => examples.push(PuzzleExample {
+                input,
+                answers: answer.map(|a| vec![(part, a)]).unwrap_or_default(),
+            }),
+            (None, Some(example)) => example.answers.extend(answer.map(|a| (part, a))),
+            (None, None) => {}
+        }
+    }
+
+    examples
+}
+
+/// A paragraph or a fenced code block of the description.
+#[derive(Debug, PartialEq, Eq)]
+enum Block {
+    Text(String),
+    Code(String),
+}
+
+fn parse_blocks(section: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut code: Option<String> = None;
+
+    for line in section.lines() {
+        match (&mut code, line.trim_start().starts_with("```")) {
+            (None, true) => code = Some(String::new()),
+            (Some(_), true) => blocks.push(Block::Code(code.take().unwrap_or_default())),
+            (Some(code), false) => {
+                code.push_str(line);
+                code.push('\n');
+            }
+            (None, false) if line.trim().is_empty() => {}
+            (None, false) => blocks.push(Block::Text(line.to_string())),
+        }
+    }
+
+    blocks
+}
+
+/// Returns the first code block that follows a paragraph mentioning an example.
+fn find_example(blocks: &[Block]) -> Option<String> {
+    blocks.windows(2).find_map(|pair| match pair {
+        [Block::Text(text), Block::Code(code)]
+            if text.to_lowercase().contains("example") && !code.trim().is_empty() =>
+        {
+            Some(code.clone())
+        }
+        _ => None,
+    })
+}
+
+/// Returns the last highlighted code span of the section, ignoring the answers submitted on the website.
+fn last_highlight(blocks: &[Block]) -> Option<String> {
+    blocks
+        .iter()
+        .filter_map(|block| match block {
+            Block::Text(text) if !text.starts_with("Your puzzle answer was") => Some(text),
+            _ => None,
+        })
+        .flat_map(|text| highlights(text))
+        .last()
+}
+
+/// Finds the code spans of a line that are emphasized, e.g. `` `*11*` `` or `` *`11`* ``.
+fn highlights(line: &str) -> Vec<String> {
+    let mut found = vec![];
+    let mut rest = line;
+
+    while let Some(start) = rest.find('`') {
+        let Some(len) = rest[start + 1..].find('`') else {
+            break;
+        };
+
+        let content = &rest[start + 1..start + 1 + len];
+        let before = rest[..start].ends_with('*');
+        let after = rest[start + len + 2..].starts_with('*');
+        let inside = content.len() > 2 && content.starts_with('*') && content.ends_with('*');
+
+        if before && after || inside {
+            let value = content.trim_matches('*').trim();
+            if !value.is_empty() {
+                found.push(value.to_string());
+            }
+        }
+
+        rest = &rest[start + len + 2..];
+    }
+
+    found
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract_examples, highlights, title, PuzzleExample};
+
+    const SHARED_EXAMPLE: &str = include_str!("fixtures/shared_example.md");
+    const SEPARATE_EXAMPLES: &str = include_str!("fixtures/separate_examples.md");
+
+    #[test]
+    fn reads_titles() {
+        assert_eq!(title(SHARED_EXAMPLE).as_deref(), Some("Sonar Sums"));
+        assert_eq!(title("# nothing here"), None);
+    }
+
+    #[test]
+    fn finds_highlights() {
+        assert_eq!(highlights("the answer is `*11*`."), vec!["11"]);
+        assert_eq!(highlights("the answer is *`11`*."), vec!["11"]);
+        assert_eq!(highlights("use `x < 3` and `**4,6**`"), vec!["4,6"]);
+        assert!(highlights("plain `code` and *emphasis*").is_empty());
+    }
+
+    #[test]
+    fn extracts_shared_examples() {
+        assert_eq!(
+            extract_examples(SHARED_EXAMPLE),
+            vec![PuzzleExample {
+                input: "199\n200\n208\n210\n".into(),
+                answers: vec![(1, "7".into()), (2, "5".into())],
+            }]
+        );
+    }
+
+    #[test]
+    fn extracts_separate_examples() {
+        let examples = extract_examples(SEPARATE_EXAMPLES);
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].input, "xmul(2,4)%&mul[3,7]\n");
+        assert_eq!(examples[0].answers, vec![(1, "161".to_string())]);
+        assert_eq!(examples[1].input, "xmul(2,4)&mul[3,7]don't()mul(5,5)\n");
+        assert_eq!(examples[1].answers, vec![(2, "48".to_string())]);
+    }
+
+    #[test]
+    fn handles_descriptions_without_examples() {
+        assert!(extract_examples("## --- Day 1: Nothing ---\n\nNo code here.").is_empty());
+    }
+}
@@ -1,19 +1,70 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::fmt::Display;
+use std::{env, fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::allocations::{format_bytes, AllocStats};
+use crate::template::stats::nanos_to_duration;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{PuzzleId, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
-#[allow(dead_code)]
+/// The chart is written next to the README.
+static CHART_FILE_NAME: &str = "benchmarks.svg";
+
+/// Extras of the benchmark table, configured with `AOC_README_BENCHMARKS`, e.g. `share,slowest,totals,chart`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TableOptions {
+    /// Add a column with each day's fraction of the total runtime.
+    pub share: bool,
+    /// Highlight the slowest day.
+    pub slowest: bool,
+    /// Add a row with the total of each part.
+    pub totals: bool,
+    /// Write a bar chart of the timings to `benchmarks.svg` and embed it below the table.
+    pub chart: bool,
+}
+
+impl TableOptions {
+    pub fn from_env() -> Self {
+        env::var("AOC_README_BENCHMARKS")
+            .map(|s| Self::parse(&s))
+            .unwrap_or_default()
+    }
+
+    fn parse(s: &str) -> Self {
+        let mut options = Self::default();
+
+        for option in s.split(',').map(str::trim).filter(|x| !x.is_empty()) {
+            match option {
+                "share" => options.share = true,
+                "slowest" => options.slowest = true,
+                "totals" => options.totals = true,
+                "chart" => options.chart = true,
+                _ => eprintln!("Warning: unknown benchmark table option `{option}`."),
+            }
+        }
+
+        options
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
@@ -21,17 +72,18 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub pos_start: usize,
+    pub pos_end: usize,
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(id: PuzzleId) -> String {
+    format!("./src/bin/{id}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Finds the table between the first and last occurence of a marker, including the markers.
+pub fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,55 +104,238 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+/// Returns the allocations of both parts of a day, with the larger peak of the two.
+fn day_allocations(timing: &Timing) -> Option<AllocStats> {
+    [1, 2]
+        .into_iter()
+        .filter_map(|part| timing.part_allocations(part).copied())
+        .reduce(add_allocations)
+}
+
+fn add_allocations(a: AllocStats, b: AllocStats) -> AllocStats {
+    AllocStats {
+        allocations: a.allocations + b.allocations,
+        bytes: a.bytes + b.bytes,
+        peak_bytes: a.peak_bytes.max(b.peak_bytes),
+    }
+}
+
+/// Formats the cells of the allocation columns, e.g. `| 120 (4.5 KiB) | 2.0 KiB |`.
+fn format_allocations(allocations: Option<AllocStats>) -> String {
+    match allocations {
+        Some(allocations) => format!(
+            " {} ({}) | {} |",
+            allocations.allocations,
+            format_bytes(allocations.bytes),
+            format_bytes(allocations.peak_bytes)
+        ),
+        None => " - | - |".into(),
+    }
+}
+
+fn construct_table(
+    prefix: &str,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+    options: TableOptions,
+) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    // NOTE: allocations are only counted with the `count-allocations` feature.
+    let has_allocations = timings
+        .data
+        .iter()
+        .any(|timing| day_allocations(timing).is_some());
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+    let mut columns = String::from("| Day | Part 1 | Part 2 |");
+    let mut alignment = String::from("| :---: | :---: | :---:  |");
+
+    if options.share {
+        columns.push_str(" Share |");
+        alignment.push_str(" :---: |");
+    }
+
+    if has_allocations {
+        columns.push_str(" Allocations | Peak |");
+        alignment.push_str(" :---: | :---: |");
+    }
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new(), columns, alignment];
+
+    let total_nanos: f64 = timings.data.iter().map(|timing| timing.total_nanos).sum();
+
+    let slowest = timings
+        .data
+        .iter()
+        .filter(|_| options.slowest)
+        .max_by(|a, b| a.total_nanos.total_cmp(&b.total_nanos))
+        .map(|timing| timing.day);
+
+    for timing in &timings.data {
+        let path = get_path_for_bin(PuzzleId::new(year, timing.day));
+        let day = format!("[Day {}]({})", timing.day.into_inner(), path);
+
+        let mut line = format!(
+            "| {} | `{}` | `{}` |",
+            if slowest == Some(timing.day) {
+                format!("**{day}** 🐢")
+            } else {
+                day
+            },
+            timing.part_1.as_deref().unwrap_or("-"),
+            timing.part_2.as_deref().unwrap_or("-")
+        );
+
+        if options.share {
+            let share = if total_nanos > 0.0 {
+                timing.total_nanos / total_nanos * 100.0
+            } else {
+                0.0
+            };
+            line.push_str(&format!(" {share:.1}% |"));
+        }
+
+        if has_allocations {
+            line.push_str(&format_allocations(day_allocations(timing)));
+        }
+
+        lines.push(line);
+    }
+
+    if options.totals {
+        let part_total = |part| {
+            let nanos: f64 = timings
+                .data
+                .iter()
+                .filter_map(|timing| timing.part_nanos(part))
+                .sum();
+            format!("{:.1?}", nanos_to_duration(nanos))
+        };
+
+        let mut line = format!("| **Total** | `{}` | `{}` |", part_total(1), part_total(2));
+        if options.share {
+            line.push_str(" 100% |");
+        }
+        if has_allocations {
+            let total = timings
+                .data
+                .iter()
+                .filter_map(day_allocations)
+                .reduce(add_allocations);
+            line.push_str(&format_allocations(total));
+        }
+        lines.push(line);
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    if options.chart {
+        lines.push(String::new());
+        lines.push(format!("![Benchmarks](./{CHART_FILE_NAME})"));
+    }
+
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/// Draws a horizontal bar chart of the timings, with the parts of a day stacked.
+fn construct_chart(timings: &Timings) -> String {
+    const WIDTH: f64 = 640.0;
+    const LABEL_WIDTH: f64 = 70.0;
+    const VALUE_WIDTH: f64 = 80.0;
+    const ROW_HEIGHT: f64 = 20.0;
+    const BAR_HEIGHT: f64 = 14.0;
+    const COLORS: [&str; 2] = ["#4e79a7", "#f28e2b"];
+
+    let max_nanos = timings
+        .data
+        .iter()
+        .map(|timing| timing.total_nanos)
+        .fold(0.0, f64::max);
+    let scale = if max_nanos > 0.0 {
+        (WIDTH - LABEL_WIDTH - VALUE_WIDTH) / max_nanos
+    } else {
+        0.0
+    };
+
+    let height = ROW_HEIGHT * timings.data.len() as f64 + 10.0;
+
+    let mut lines = vec![format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" font-family="sans-serif" font-size="12">"#
+    )];
+
+    for (index, timing) in timings.data.iter().enumerate() {
+        let y = 5.0 + ROW_HEIGHT * index as f64;
+        let text_y = y + BAR_HEIGHT - 3.0;
+
+        lines.push(format!(
+            r#"  <text x="0" y="{text_y}">Day {}</text>"#,
+            timing.day.into_inner()
+        ));
+
+        let mut x = LABEL_WIDTH;
+        for (part, color) in [1, 2].into_iter().zip(COLORS) {
+            let Some(nanos) = timing.part_nanos(part) else {
+                continue;
+            };
+            let width = nanos * scale;
+            lines.push(format!(
+                r#"  <rect x="{x:.1}" y="{y}" width="{width:.1}" height="{BAR_HEIGHT}" fill="{color}"><title>Part {part}: {:.1?}</title></rect>"#,
+                nanos_to_duration(nanos)
+            ));
+            x += width;
+        }
+
+        lines.push(format!(
+            r#"  <text x="{:.1}" y="{text_y}">{:.1?}</text>"#,
+            x + 5.0,
+            nanos_to_duration(timing.total_nanos)
+        ));
+    }
+
+    lines.push("</svg>".into());
+    lines.join("\n") + "\n"
+}
+
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+    options: TableOptions,
+) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", year, timings, total_millis, options);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+pub fn update(year: Year, mut timings: Timings) -> Result<(), Error> {
+    // NOTE: skip stored days that are not part of the event, e.g. after changing the day count.
+    timings.data.retain(|timing| year.has_day(timing.day));
+
+    let options = TableOptions::from_env();
+
+    if options.chart {
+        fs::write(CHART_FILE_NAME, construct_chart(&timings))?;
+    }
+
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, timings, total_millis, options)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{construct_chart, update_content, TableOptions, MARKER};
+    use crate::template::allocations::AllocStats;
+    use crate::{day, template::timings::Timing, template::timings::Timings, year};
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -109,18 +344,30 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
                     total_nanos: 9e+10,
                 },
             ],
@@ -131,28 +378,63 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2024),
+            get_mock_timings(),
+            190.0,
+            TableOptions::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2024),
+            get_mock_timings(),
+            190.0,
+            TableOptions::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2024),
+            get_mock_timings(),
+            190.0,
+            TableOptions::default(),
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2024),
+            get_mock_timings(),
+            190.0,
+            TableOptions::default(),
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            year!(2024),
+            get_mock_timings(),
+            190.0,
+            TableOptions::default(),
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +442,14 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2024),
+            get_mock_timings(),
+            190.0,
+            TableOptions::default(),
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
@@ -169,9 +458,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2024_01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2024_02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2024_04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +469,98 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn parses_table_options() {
+        assert_eq!(TableOptions::parse(""), TableOptions::default());
+        assert_eq!(
+            TableOptions::parse("share, totals"),
+            TableOptions {
+                share: true,
+                totals: true,
+                ..TableOptions::default()
+            }
+        );
+    }
+
+    #[test]
+    fn format_benchmarks_with_extras() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let options = TableOptions {
+            share: true,
+            slowest: true,
+            totals: true,
+            chart: true,
+        };
+        update_content(&mut s, year!(2024), get_mock_timings(), 190.0, options).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 | Share |",
+            "| :---: | :---: | :---:  | :---: |",
+            "| [Day 1](./src/bin/2024_01.rs) | `10ms` | `20ms` | 15.8% |",
+            "| [Day 2](./src/bin/2024_02.rs) | `30ms` | `40ms` | 36.8% |",
+            "| **[Day 4](./src/bin/2024_04.rs)** 🐢 | `40ms` | `50ms` | 47.4% |",
+            "| **Total** | `80.0ms` | `110.0ms` | 100% |",
+            "",
+            "**Total: 190.00ms**",
+            "",
+            "![Benchmarks](./benchmarks.svg)",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_allocations() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_allocations = Some(AllocStats {
+            allocations: 12,
+            bytes: 4096,
+            peak_bytes: 2048,
+        });
+        timings.data[0].part_2_allocations = Some(AllocStats {
+            allocations: 3,
+            bytes: 512,
+            peak_bytes: 3072,
+        });
+        timings.data[1].part_1_allocations = Some(AllocStats::default());
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let options = TableOptions {
+            totals: true,
+            ..TableOptions::default()
+        };
+        update_content(&mut s, year!(2024), timings, 190.0, options).unwrap();
+        let lines: Vec<&str> = s.lines().collect();
+
+        assert_eq!(lines[3], "| Day | Part 1 | Part 2 | Allocations | Peak |");
+        assert_eq!(lines[4], "| :---: | :---: | :---:  | :---: | :---: |");
+        assert_eq!(
+            lines[5],
+            "| [Day 1](./src/bin/2024_01.rs) | `10ms` | `20ms` | 15 (4.5 KiB) | 3.0 KiB |"
+        );
+        assert_eq!(
+            lines[6],
+            "| [Day 2](./src/bin/2024_02.rs) | `30ms` | `40ms` | 0 (0 B) | 0 B |"
+        );
+        assert_eq!(
+            lines[7],
+            "| [Day 4](./src/bin/2024_04.rs) | `40ms` | `50ms` | - | - |"
+        );
+        assert_eq!(
+            lines[8],
+            "| **Total** | `80.0ms` | `110.0ms` | 15 (4.5 KiB) | 3.0 KiB |"
+        );
+    }
+
+    #[test]
+    fn draws_charts() {
+        let chart = construct_chart(&get_mock_timings());
+        assert!(chart.starts_with("<svg"));
+        assert_eq!(chart.matches("<rect").count(), 6);
+        assert!(chart.contains("Day 4"));
+    }
 }
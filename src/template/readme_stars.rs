@@ -0,0 +1,157 @@
+/// Module that updates the stars table of the readme from the verified answers.
+/// Replaces the table that is otherwise maintained by `aoc-readme-stars`, using the same markers.
+use std::fs;
+
+use crate::template::answers::{Outcome, Verification};
+use crate::template::readme_benchmarks::{locate_table, Error};
+use crate::template::{all_days, Day, Year};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// Stars of a single day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct DayStars {
+    day: Day,
+    part_1: bool,
+    part_2: bool,
+}
+
+/// Collects the stars of every day of the event. A part earns a star if its solution ran and matched the stored
+/// answer. The second part of the last day is earned by collecting all other stars, as on the website.
+fn collect_stars(year: Year, verifications: &[Verification]) -> Vec<DayStars> {
+    let passed = |day: Day, part: u8| {
+        verifications
+            .iter()
+            .any(|v| v.day == day && v.part == part && v.outcome == Outcome::Pass)
+    };
+
+    let mut stars: Vec<DayStars> = all_days(year)
+        .map(|day| DayStars {
+            day,
+            part_1: passed(day, 1),
+            part_2: passed(day, 2),
+        })
+        .collect();
+
+    let all_others_earned = stars
+        .split_last()
+        .is_some_and(|(last, others)| last.part_1 && others.iter().all(|s| s.part_1 && s.part_2));
+
+    if let Some(last) = stars.last_mut() {
+        last.part_2 |= all_others_earned;
+    }
+
+    stars
+}
+
+fn construct_table(prefix: &str, year: Year, stars: &[DayStars]) -> String {
+    let star = |earned: bool| if earned { "⭐" } else { " " };
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        format!("{prefix} {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for day in stars {
+        lines.push(format!(
+            "| [Day {}](https://adventofcode.com/{year}/day/{}) | {} | {} |",
+            day.day.into_inner(),
+            day.day.into_inner(),
+            star(day.part_1),
+            star(day.part_2)
+        ));
+    }
+
+    lines.push(MARKER.into());
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, year: Year, verifications: &[Verification]) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", year, &collect_stars(year, verifications));
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update(year: Year, verifications: &[Verification]) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, year, verifications)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{collect_stars, update_content, MARKER};
+    use crate::template::answers::{Outcome, Verification};
+    use crate::template::{all_days, Day};
+    use crate::{day, year};
+
+    fn verification(day: Day, part: u8, outcome: Outcome) -> Verification {
+        Verification {
+            day,
+            part,
+            expected: None,
+            actual: None,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn collects_stars_of_passing_parts_only() {
+        let stars = collect_stars(
+            year!(2024),
+            &[
+                verification(day!(1), 1, Outcome::Pass),
+                verification(day!(1), 2, Outcome::Fail),
+                verification(day!(2), 1, Outcome::Missing),
+                Verification {
+                    expected: Some("42".into()),
+                    ..verification(day!(3), 1, Outcome::Skipped)
+                },
+                verification(day!(3), 2, Outcome::Skipped),
+            ],
+        );
+        assert_eq!(stars.len(), 25);
+        assert!(stars[0].part_1);
+        assert!(!stars[0].part_2);
+        assert!(!stars[1].part_1);
+        assert!(!stars[2].part_1);
+        assert!(!stars[2].part_2);
+        assert!(!stars[24].part_2);
+    }
+
+    #[test]
+    fn awards_last_star_for_all_others() {
+        let verifications: Vec<Verification> = all_days(year!(2024))
+            .flat_map(|day| [1, 2].map(|part| verification(day, part, Outcome::Pass)))
+            .filter(|v| !(v.day == day!(25) && v.part == 2))
+            .collect();
+
+        let stars = collect_stars(year!(2024), &verifications);
+        assert!(stars[24].part_2);
+    }
+
+    #[test]
+    fn updates_stars_table() {
+        let mut s = format!("foo\n{MARKER}\n## old\n{MARKER}\nbar");
+        update_content(
+            &mut s,
+            year!(2025),
+            &[verification(day!(3), 2, Outcome::Pass)],
+        )
+        .unwrap();
+
+        assert!(s.starts_with("foo\n<!--- advent_readme_stars table --->\n## 2025 Results\n"));
+        assert!(s.contains("| [Day 3](https://adventofcode.com/2025/day/3) |   | ⭐ |"));
+        assert!(s.contains("| [Day 12](https://adventofcode.com/2025/day/12) |"));
+        assert!(!s.contains("## old"));
+        assert!(s.ends_with(&format!("{MARKER}\nbar")));
+    }
+}
@@ -1,257 +1,611 @@
-use std::{collections::HashSet, io};
-
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::any::Any;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Instant;
+
+use crate::template::output::{
+    capture, emit_record, emit_summary, is_capturing, write_text, Status,
+};
+use crate::template::runner::{on_first_run, PartFn, PartResult, RunOptions, Solution};
+use crate::template::{
+    get_data_path_part, Day, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Results of running a set of days.
+pub struct RunReport {
+    /// Results of every part, including parts that could not be run.
+    pub results: Vec<PartResult>,
+    /// Timings of all days that were run, if the run was timed.
+    pub timings: Option<Timings>,
+}
+
+impl RunReport {
+    /// Number of parts that failed, crashed or timed out.
+    pub fn failures(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|r| r.status.is_failure())
+            .count()
+    }
+}
+
+/// Runs the registered solutions for the passed days of a year in-process.
+/// Untimed runs execute up to `options.jobs` days in parallel, their output is still printed in order of the days.
+/// Timed runs stop after a day that timed out, the remaining days are not run.
+pub fn run_multi(
+    solutions: &[Solution],
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    options: &RunOptions,
+) -> RunReport {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut records: Vec<PartResult> = vec![];
 
-    let mut need_space = false;
+    let is_text = options.format.is_text();
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let days: Vec<Day> = all_days(year)
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
+        .collect();
+
+    // NOTE: benchmarks stay serial, days running in parallel would distort each other's timings.
+    let jobs = if options.is_timed { 1 } else { options.jobs };
+
+    if jobs > 1 {
+        run_parallel(
+            &days,
+            jobs,
+            |day| capture(|| run_day(solutions, year, *day, options)),
+            |index, ((results, timing), text)| {
+                if is_text && index > 0 {
+                    println!();
+                }
+                write_text(&text);
+
+                timings.extend(timing);
+                records.extend(results);
+            },
+        );
+    } else {
+        for (index, day) in days.iter().enumerate() {
+            if is_text && index > 0 {
                 println!();
             }
-            need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+            let (results, timing) = run_day(solutions, year, *day, options);
+            let timed_out = results.iter().any(|r| r.status == Status::TimedOut);
+            timings.extend(timing);
+            records.extend(results);
+
+            // NOTE: an abandoned part keeps a core busy, it would skew the timings of the remaining days.
+            let remaining = days.len() - index - 1;
+            if options.is_timed && timed_out && remaining > 0 {
+                eprintln!(
+                    "Warning: day {day} timed out and keeps running, the remaining {remaining} day(s) were not benchmarked."
+                );
+                break;
+            }
+        }
+    }
+
+    emit_summary(&records, options.format);
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    if is_text {
+        println!("\n{ANSI_BOLD}Summary:{ANSI_RESET} {}", summarize(&records));
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+        for result in records.iter().filter(|r| r.error.is_some()) {
+            println!("{}", describe_failure(result));
+        }
+    }
 
-    if is_timed {
+    let timings = options.is_timed.then(|| {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
-        Some(timings)
-    } else {
-        None
-    }
-}
+        if is_text {
+            println!(
+                "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
+        timings
+    });
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    IO(io::Error),
+    RunReport {
+        results: records,
+        timings,
+    }
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+/// Runs the parts of a day. Returns their results, and the day's timing if it could be run.
+fn run_day(
+    solutions: &[Solution],
+    year: Year,
+    day: Day,
+    options: &RunOptions,
+) -> (Vec<PartResult>, Option<Timing>) {
+    let is_text = options.format.is_text();
+
+    if is_text {
+        write_text(&format!("{ANSI_BOLD}Day {day}{ANSI_RESET}\n------\n"));
     }
-}
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
+    let id = PuzzleId::new(year, day);
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+    // skip days that have not been scaffolded yet.
+    let Some(solution) = solutions.iter().find(|s| s.year == year && s.day == day) else {
+        if is_text {
+            write_text("Not implemented.\n");
+        }
+        return (skip_day(id, Status::NotImplemented, options), None);
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    // NOTE: parts may have an input file of their own.
+    let Ok(inputs) = solution
+        .parts
+        .iter()
+        .map(|(part, _)| fs::read_to_string(get_data_path_part("inputs", id, *part)))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        if is_text {
+            write_text("Input file missing.\n");
         }
+        return (skip_day(id, Status::MissingInput, options), None);
+    };
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
+    let mut results: Vec<PartResult> = Vec::with_capacity(solution.parts.len());
+
+    for ((part, run), input) in solution.parts.iter().zip(&inputs) {
+        // NOTE: once a part crashed or timed out, the remaining parts of the day are skipped.
+        if let Some(status) = results
+            .last()
+            .map(|r| r.status)
+            .filter(|s| matches!(s, Status::Crashed | Status::TimedOut))
+        {
+            let result = PartResult::skipped(id, *part, status);
+            emit_record(&result, options.format);
+            results.push(result);
+            continue;
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        results.push(run_isolated(*run, id, *part, input, options));
+    }
+
+    let timing = to_timing(day, &results);
+    (results, Some(timing))
+}
+
+/// Runs `f` for each item on up to `jobs` threads. Passes the results with the index of their item
+/// to `on_result`, in order of the items and as soon as all earlier items are done.
+fn run_parallel<T: Sync, R: Send>(
+    items: &[T],
+    jobs: usize,
+    f: impl Fn(&T) -> R + Sync,
+    mut on_result: impl FnMut(usize, R),
+) {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            let sender = sender.clone();
+            let (next, f) = (&next, &f);
+
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                if sender.send((index, f(item))).is_err() {
+                    break;
+                }
+            });
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // NOTE: drop the original sender, so that the receiver stops once all workers are done.
+        drop(sender);
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        let mut pending = BTreeMap::new();
+        let mut next_index = 0;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next_index) {
+                on_result(next_index, result);
+                next_index += 1;
+            }
+        }
+    });
+}
 
-        let mut output = vec![];
+/// Progress of a part that runs on a thread of its own.
+enum Progress {
+    /// The first run finished, the part is being benched.
+    FirstRun,
+    /// The part finished, with the text it wrote.
+    Done(Box<PartResult>, String),
+}
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+/// Runs a part on a thread of its own, so that a panic or an endless loop does not take down the run.
+///
+/// The timeout only applies to the first run, benching a part takes as many iterations as it needs.
+/// Threads can not be stopped: a part that timed out is abandoned and keeps running in the background,
+/// using a core and its memory, until the process exits.
+fn run_isolated(
+    run: PartFn,
+    id: PuzzleId,
+    part: u8,
+    input: &str,
+    options: &RunOptions,
+) -> PartResult {
+    let (sender, receiver) = mpsc::channel();
+    let input = input.to_string();
+    let thread_options = *options;
+    let is_captured = is_capturing();
+
+    // NOTE: match the stack size of the main thread, solutions may recurse deeply.
+    let handle = thread::Builder::new()
+        .name(format!("{id} part {part}"))
+        .stack_size(8 * 1024 * 1024)
+        .spawn(move || {
+            let first_run = sender.clone();
+            on_first_run(move || {
+                let _ = first_run.send(Progress::FirstRun);
             });
+
+            // NOTE: output is captured per thread, pass it on to the day's thread.
+            let (result, text) = if is_captured {
+                capture(|| run(&input, &thread_options))
+            } else {
+                (run(&input, &thread_options), String::new())
+            };
+            let _ = sender.send(Progress::Done(Box::new(result), text));
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+    let handle = match handle {
+        Ok(handle) => handle,
+        Err(e) => return abandon_part(id, part, Status::Crashed, e.to_string(), options),
+    };
+
+    let mut deadline = options.timeout.map(|timeout| Instant::now() + timeout);
+
+    let received = loop {
+        let progress = match deadline {
+            Some(deadline) => {
+                receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        match progress {
+            Ok(Progress::FirstRun) => deadline = None,
+            Ok(Progress::Done(result, text)) => break Ok((result, text)),
+            Err(e) => break Err(e),
+        }
+    };
 
-        Ok(output)
+    match received {
+        Ok((result, text)) => {
+            write_text(&text);
+            *result
+        }
+        // NOTE: the thread is abandoned, see above.
+        Err(RecvTimeoutError::Timeout) => {
+            let timeout = options.timeout.unwrap_or_default();
+            let message = format!("did not finish within {timeout:?}");
+            abandon_part(id, part, Status::TimedOut, message, options)
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            let message = match handle.join() {
+                Err(payload) => panic_message(payload.as_ref()),
+                Ok(()) => "exited without a result".into(),
+            };
+            abandon_part(id, part, Status::Crashed, message, options)
+        }
     }
+}
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+/// Creates, prints and emits the record of a part that crashed or timed out.
+fn abandon_part(
+    id: PuzzleId,
+    part: u8,
+    status: Status,
+    message: String,
+    options: &RunOptions,
+) -> PartResult {
+    if options.format.is_text() {
+        let label = if status == Status::TimedOut {
+            "timed out"
+        } else {
+            "crashed"
         };
+        write_text(&format!("Part {part}: ✖ {label}\n"));
+    }
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+    let result = PartResult {
+        error: Some(message),
+        ..PartResult::skipped(id, part, status)
+    };
+    emit_record(&result, options.format);
+    result
+}
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+/// Extracts the message of a panic, which is either a `&str` or a `String`.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panicked".into()
+    }
+}
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+/// Describes a part that failed, crashed or timed out for the summary.
+fn describe_failure(result: &PartResult) -> String {
+    let label = match result.status {
+        Status::Crashed => "crashed",
+        Status::TimedOut => "timed out",
+        _ => "failed",
+    };
 
-                timings.total_nanos += nanos;
-            });
+    format!(
+        "  Day {} Part {}: {label}: {}",
+        result.day,
+        result.part,
+        result.error.as_deref().unwrap_or_default()
+    )
+}
 
-        timings
+/// Counts the parts of a run by status, e.g. `46 solved, 1 failed, 2 not implemented`.
+fn summarize(results: &[PartResult]) -> String {
+    let statuses = [
+        (Status::Solved, "solved"),
+        (Status::NoAnswer, "without answer"),
+        (Status::Failed, "failed"),
+        (Status::Crashed, "crashed"),
+        (Status::TimedOut, "timed out"),
+        (Status::NotImplemented, "not implemented"),
+        (Status::MissingInput, "missing input"),
+    ];
+
+    statuses
+        .iter()
+        .filter_map(|(status, label)| {
+            let count = results.iter().filter(|r| r.status == *status).count();
+            (count > 0).then(|| format!("{count} {label}"))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Creates and emits records for both parts of a day that could not be run.
+fn skip_day(id: PuzzleId, status: Status, options: &RunOptions) -> Vec<PartResult> {
+    [1, 2]
+        .into_iter()
+        .map(|part| {
+            let result = PartResult::skipped(id, part, status);
+            emit_record(&result, options.format);
+            result
+        })
+        .collect()
+}
+
+/// Collects the results of a day's parts into a [`Timing`]. Parts without an answer are not timed.
+fn to_timing(day: Day, results: &[PartResult]) -> Timing {
+    let mut timing = Timing {
+        day,
+        part_1: None,
+        part_2: None,
+        part_1_stats: None,
+        part_2_stats: None,
+        part_1_allocations: None,
+        part_2_allocations: None,
+        total_nanos: 0_f64,
+    };
+
+    for result in results.iter().filter(|r| r.answer.is_some()) {
+        let duration_str = format!("{:.1?}", result.duration);
+
+        match result.part {
+            1 => {
+                timing.part_1 = Some(duration_str);
+                timing.part_1_stats.clone_from(&result.stats);
+                timing.part_1_allocations = result.allocations;
+            }
+            2 => {
+                timing.part_2 = Some(duration_str);
+                timing.part_2_stats.clone_from(&result.stats);
+                timing.part_2_allocations = result.allocations;
+            }
+            _ => continue,
+        }
+
+        timing.total_nanos += result.duration.as_nanos() as f64;
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    timing
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{describe_failure, run_isolated, run_parallel, summarize, to_timing, RunReport};
+    use crate::template::output::OutputFormat;
+    use crate::template::runner::{execute_part, RunOptions};
+    use crate::{
+        day,
+        template::{output::Status, runner::PartResult, PuzzleId},
+        year,
+    };
+
+    fn result(part: u8, answer: Option<&str>, nanos: u64) -> PartResult {
+        PartResult {
+            year: year!(2024),
+            day: day!(1),
+            part,
+            answer: answer.map(String::from),
+            duration: Duration::from_nanos(nanos),
+            stats: None,
+            allocations: None,
+            status: if answer.is_some() {
+                Status::Solved
+            } else {
+                Status::NoAnswer
+            },
+            error: None,
+        }
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    #[test]
+    fn collects_part_timings() {
+        let timing = to_timing(
+            day!(1),
+            &[
+                result(1, Some("0"), 74_130),
+                result(2, Some("10"), 2_000_000),
+            ],
+        );
+        assert_eq!(timing.total_nanos, 2_074_130_f64);
+        assert_eq!(timing.part_1.unwrap(), "74.1µs");
+        assert_eq!(timing.part_2.unwrap(), "2.0ms");
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    #[test]
+    fn skips_missing_answers() {
+        let timing = to_timing(day!(1), &[result(1, None, 100), result(2, None, 100)]);
+        assert_eq!(timing.total_nanos, 0_f64);
+        assert_eq!(timing.part_1.is_none(), true);
+        assert_eq!(timing.part_2.is_none(), true);
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
+    #[test]
+    fn handles_single_part_solutions() {
+        let timing = to_timing(day!(25), &[result(1, Some("1"), 1_000)]);
+        assert_eq!(timing.part_1.unwrap(), "1.0µs");
+        assert_eq!(timing.part_2.is_none(), true);
+    }
 
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+    #[test]
+    fn summarizes_statuses() {
+        let mut failed = result(2, None, 100);
+        failed.status = Status::Failed;
+        let results = [
+            result(1, Some("1"), 100),
+            failed,
+            PartResult::skipped(
+                PuzzleId::new(year!(2024), day!(2)),
+                1,
+                Status::NotImplemented,
+            ),
+            PartResult::skipped(
+                PuzzleId::new(year!(2024), day!(2)),
+                2,
+                Status::NotImplemented,
+            ),
+        ];
+        assert_eq!(summarize(&results), "1 solved, 1 failed, 2 not implemented");
+    }
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+    #[test]
+    fn counts_failures() {
+        let mut crashed = result(2, None, 100);
+        crashed.status = Status::Crashed;
+        let report = RunReport {
+            results: vec![result(1, Some("1"), 100), result(1, None, 100), crashed],
+            timings: None,
+        };
+        assert_eq!(report.failures(), 1);
+    }
+
+    #[test]
+    fn captures_panics() {
+        let result = run_isolated(
+            |_, _| panic!("boom"),
+            PuzzleId::new(year!(2024), day!(3)),
+            1,
+            "",
+            &RunOptions::default(),
+        );
+        assert_eq!(result.status, Status::Crashed);
+        assert_eq!(result.error.as_deref(), Some("boom"));
+        assert_eq!(describe_failure(&result), "  Day 03 Part 1: crashed: boom");
+    }
+
+    #[test]
+    fn abandons_parts_after_timeout() {
+        let options = RunOptions {
+            timeout: Some(Duration::from_millis(10)),
+            ..RunOptions::default()
+        };
+        let result = run_isolated(
+            |_, _| loop {
+                std::thread::sleep(Duration::from_millis(100));
+            },
+            PuzzleId::new(year!(2024), day!(14)),
+            2,
+            "",
+            &options,
+        );
+        assert_eq!(result.status, Status::TimedOut);
+    }
+
+    #[test]
+    fn does_not_time_out_while_benching() {
+        let options = RunOptions {
+            is_timed: true,
+            format: OutputFormat::Json,
+            timeout: Some(Duration::from_millis(50)),
+            ..RunOptions::default()
+        };
+        let result = run_isolated(
+            |input, options| {
+                execute_part(
+                    |_: &str| {
+                        std::thread::sleep(Duration::from_millis(5));
+                        Some(1)
+                    },
+                    input,
+                    PuzzleId::new(year!(2024), day!(14)),
+                    1,
+                    options,
+                )
+            },
+            PuzzleId::new(year!(2024), day!(14)),
+            1,
+            "",
+            &options,
+        );
+        assert_eq!(result.status, Status::Solved);
+        assert!(result.samples() > 10);
+    }
+
+    #[test]
+    fn passes_parallel_results_in_order() {
+        let items: Vec<u64> = (0..8).collect();
+        let mut results = vec![];
+
+        run_parallel(
+            &items,
+            4,
+            |item| {
+                // NOTE: let earlier items finish last.
+                std::thread::sleep(Duration::from_millis(16 - item * 2));
+                item * 10
+            },
+            |index, result| results.push((index, result)),
+        );
+
+        assert_eq!(
+            results,
+            (0..8).map(|i| (i as usize, i * 10)).collect::<Vec<_>>()
+        );
     }
 }
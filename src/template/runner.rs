@@ -1,64 +1,335 @@
 /// Encapsulates code that interacts with solution functions.
+use std::cell::RefCell;
+use std::error::Error;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env};
 
+use crate::template::allocations::{self, format_bytes, AllocStats};
+use crate::template::answers::Answers;
+use crate::template::output::{emit_record, write_text, OutputFormat, Status};
+use crate::template::stats::{nanos_to_duration, BenchStats};
+use crate::template::submissions::{Check, Submissions, Verdict};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, aoc_client, Day, PuzzleId, Year, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Fraction (1/n) of the bench iterations that are run as warm-up and discarded.
+const WARMUP_DIVISOR: u128 = 10;
+
+/// Default time the first run of a part may take in the multi-day runner before it is abandoned.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A day's solution, registered for in-process execution by the multi-day runner.
+/// Instances are created by the [`solution!`](crate::solution) macro.
+pub struct Solution {
+    pub year: Year,
+    pub day: Day,
+    pub parts: &'static [(u8, PartFn)],
+}
+
+/// Runs a single part of a solution against the passed input.
+pub type PartFn = fn(&str, &RunOptions) -> PartResult;
+
+/// Options that control how a part is run and reported.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    /// Bench the part instead of running it once.
+    pub is_timed: bool,
+    pub format: OutputFormat,
+    /// Time the first run of a part may take in the multi-day runner before it is abandoned.
+    /// Benching a part is not limited. Waits indefinitely if unset.
+    pub timeout: Option<Duration>,
+    /// Number of days the multi-day runner may run in parallel. Timed runs are always serial.
+    pub jobs: usize,
+    /// Only runs this part in a solution binary, selected with `--part`.
+    pub part: Option<u8>,
+    /// Repeats a part for at least this long after its first run, selected with `--repeat <SECS>`.
+    /// Used to sample short parts with `perf`.
+    pub repeat: Option<Duration>,
+}
+
+impl RunOptions {
+    /// Reads the options from the command-line arguments of a solution binary.
+    pub fn from_args() -> Result<Self, crate::template::Error> {
+        let args: Vec<String> = env::args().collect();
+
+        let format = match args.iter().position(|x| x == "--format") {
+            Some(index) => {
+                let Some(Ok(format)) = args.get(index + 1).map(|x| x.parse()) else {
+                    return Err(crate::template::Error::Usage(
+                        "unexpected command-line input. Format: cargo solve 1 --format json".into(),
+                    ));
+                };
+                format
+            }
+            None => OutputFormat::default(),
+        };
+
+        let part = match args.iter().position(|x| x == "--part") {
+            Some(index) => match args.get(index + 1).map(|x| x.parse()) {
+                Some(Ok(part @ (1 | 2))) => Some(part),
+                _ => {
+                    return Err(crate::template::Error::Usage(
+                        "unexpected command-line input. Format: cargo solve 1 --part 2".into(),
+                    ))
+                }
+            },
+            None => None,
+        };
+
+        let repeat = match args.iter().position(|x| x == "--repeat") {
+            Some(index) => match args.get(index + 1).map(|x| x.parse()) {
+                Some(Ok(secs)) => Some(Duration::from_secs(secs)),
+                _ => {
+                    return Err(crate::template::Error::Usage(
+                        "unexpected command-line input. Format: cargo solve 1 --repeat 2".into(),
+                    ))
+                }
+            },
+            None => None,
+        };
+
+        Ok(Self {
+            is_timed: args.iter().any(|x| x == "--time"),
+            format,
+            timeout: None,
+            jobs: 1,
+            part,
+            repeat,
+        })
+    }
+
+    /// Returns whether a solution binary runs a part.
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|selected| selected == part)
+    }
+}
+
+/// The return value of a solution part: `Option<T>`, or `Result<T, E>` for parts that can fail.
+pub trait Answer {
+    /// Returns the answer as a string, [`None`] if the part has no answer, or the chain of error messages.
+    fn to_answer(&self) -> Result<Option<String>, Vec<String>>;
+}
+
+impl<T: Display> Answer for Option<T> {
+    fn to_answer(&self) -> Result<Option<String>, Vec<String>> {
+        Ok(self.as_ref().map(ToString::to_string))
+    }
+}
+
+impl<T: Display, E: Error> Answer for Result<T, E> {
+    fn to_answer(&self) -> Result<Option<String>, Vec<String>> {
+        match self {
+            Ok(answer) => Ok(Some(answer.to_string())),
+            Err(e) => Err(error_chain(e)),
+        }
+    }
+}
+
+/// Collects the messages of an error and its sources.
+fn error_chain(e: &dyn Error) -> Vec<String> {
+    let mut messages = vec![e.to_string()];
+    let mut source = e.source();
+    while let Some(e) = source {
+        messages.push(e.to_string());
+        source = e.source();
+    }
+    messages
+}
+
+/// The outcome of running a single part of a solution.
+#[derive(Clone, Debug)]
+pub struct PartResult {
+    pub year: Year,
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub duration: Duration,
+    pub stats: Option<BenchStats>,
+    /// Allocations of the first run, if the counting allocator is installed.
+    pub allocations: Option<AllocStats>,
+    pub status: Status,
+    /// The error chain of a failed part, joined with `: `.
+    pub error: Option<String>,
+}
+
+impl PartResult {
+    /// A result for a part that could not be run.
+    pub fn skipped(id: PuzzleId, part: u8, status: Status) -> Self {
+        Self {
+            year: id.year,
+            day: id.day,
+            part,
+            answer: None,
+            duration: Duration::ZERO,
+            stats: None,
+            allocations: None,
+            status,
+            error: None,
+        }
+    }
+
+    pub fn samples(&self) -> u128 {
+        self.stats.as_ref().map_or(1, |stats| stats.samples)
+    }
+}
+
+pub fn run_part<I: Copy, A: Answer>(
+    func: impl Fn(I) -> A,
+    input: I,
+    id: PuzzleId,
+    part: u8,
+) -> Result<PartResult, crate::template::Error> {
+    let options = RunOptions::from_args()?;
+    let result = execute_part(func, input, id, part, &options);
+
+    if let Some(answer) = &result.answer {
+        submit_result(answer, id, part)?;
+    }
+
+    Ok(result)
+}
+
+/// Run and report a solution part, returning its answer and timings.
+pub fn execute_part<I: Copy, A: Answer>(
+    func: impl Fn(I) -> A,
+    input: I,
+    id: PuzzleId,
+    part: u8,
+    options: &RunOptions,
+) -> PartResult {
     let part_str = format!("Part {part}");
+    let is_text = options.format.is_text();
+
+    let (result, duration, stats, allocations) = run_timed(
+        func,
+        input,
+        |result| {
+            if is_text {
+                print_result(&result.to_answer(), &part_str, "");
+            }
+        },
+        options,
+    );
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let result = result.to_answer();
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    if is_text {
+        let samples = stats.as_ref().map_or(1, |stats| stats.samples);
+        print_result(&result, &part_str, &format_duration(&duration, samples));
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+        if let Some(stats) = &stats {
+            write_text(&format!("{}\n", format_stats(stats)));
+        }
+
+        if let Some(allocations) = &allocations {
+            write_text(&format!("{}\n", format_allocations(allocations)));
+        }
+    }
+
+    if let Err(messages) = &result {
+        print_error(id.day, part, messages);
     }
+
+    let result = match result {
+        Ok(answer) => PartResult {
+            year: id.year,
+            day: id.day,
+            part,
+            status: if answer.is_some() {
+                Status::Solved
+            } else {
+                Status::NoAnswer
+            },
+            answer,
+            duration,
+            stats,
+            allocations,
+            error: None,
+        },
+        Err(messages) => PartResult {
+            error: Some(messages.join(": ")),
+            ..PartResult::skipped(id, part, Status::Failed)
+        },
+    };
+
+    emit_record(&result, options.format);
+    result
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+thread_local! {
+    static FIRST_RUN_HOOK: RefCell<Option<Box<dyn FnOnce()>>> = const { RefCell::new(None) };
+}
+
+/// Calls `notify` once the first run of the next part on the current thread finished, before the part is benched.
+pub fn on_first_run(notify: impl FnOnce() + 'static) {
+    FIRST_RUN_HOOK.with(|hook| *hook.borrow_mut() = Some(Box::new(notify)));
+}
+
+/// Run a solution part. The behavior differs depending on whether the part is timed:
+///  1. if not, the function is executed once.
+///  2. if so, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// When benched, the returned duration is the median of all samples.
+/// The allocations are counted on the first execution, if the counting allocator is installed.
+/// Repetitions requested with `--repeat` follow the first execution and are not measured.
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    options: &RunOptions,
+) -> (T, Duration, Option<BenchStats>, Option<AllocStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, allocations) = {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        allocations::measure(|| func(input))
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
-    };
+    if let Some(notify) = FIRST_RUN_HOOK.with(|hook| hook.borrow_mut().take()) {
+        notify();
+    }
 
-    (result, run.0, run.1)
-}
+    if let Some(repeat) = options.repeat {
+        let timer = Instant::now();
+        while timer.elapsed() < repeat {
+            black_box(func(black_box(input)));
+        }
+    }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+    if options.is_timed {
+        let stats = bench(func, input, &base_time, options.format.is_text());
+        (result, stats.headline(), Some(stats), allocations)
+    } else {
+        (result, base_time, None, allocations)
+    }
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    show_progress: bool,
+) -> BenchStats {
+    if show_progress {
+        write_text(&format!(" > {ANSI_ITALIC}benching{ANSI_RESET}"));
+    }
 
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    // discard the first iterations so caches and branch predictors are warm when sampling.
+    let warmup_iterations = (bench_iterations / WARMUP_DIVISOR).max(1);
+
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input)));
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
@@ -67,19 +338,8 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    BenchStats::from_samples(&timers, warmup_iterations)
+        .expect("bench should collect at least one sample")
 }
 
 fn format_duration(duration: &Duration, samples: u128) -> String {
@@ -90,76 +350,184 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn format_stats(stats: &BenchStats) -> String {
+    format!(
+        "  ↳ min {:.1?} | median {:.1?} | p95 {:.1?} | mean {:.1?} ± {:.1?} | σ {:.1?} | outliers {} | warmup {}",
+        nanos_to_duration(stats.min),
+        nanos_to_duration(stats.median),
+        nanos_to_duration(stats.p95),
+        nanos_to_duration(stats.mean),
+        nanos_to_duration(stats.ci_95),
+        nanos_to_duration(stats.std_dev),
+        stats.outliers,
+        stats.warmup,
+    )
+}
+
+fn format_allocations(allocations: &AllocStats) -> String {
+    format!(
+        "  ↳ allocations {} | allocated {} | peak {}",
+        allocations.allocations,
+        format_bytes(allocations.bytes),
+        format_bytes(allocations.peak_bytes),
+    )
+}
+
+/// Prints the error chain of a failed part to stderr.
+fn print_error(day: Day, part: u8, messages: &[String]) {
+    let mut messages = messages.iter();
+    if let Some(message) = messages.next() {
+        eprintln!("Error: day {day}, part {part} failed: {message}");
+    }
+    for message in messages {
+        eprintln!("  caused by: {message}");
+    }
+}
+
+fn print_result(result: &Result<Option<String>, Vec<String>>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
-            } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
-            }
-        }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
-            }
+    let (str, multiline_result) = match result {
+        Ok(Some(result)) if result.contains('\n') => {
+            (format!("{part}: ▼ {duration_str}"), Some(result))
         }
+        Ok(Some(result)) => (
+            format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}"),
+            None,
+        ),
+        Ok(None) if is_intermediate_result => (format!("{part}: ✖"), None),
+        Ok(None) => (format!("{part}: ✖             "), None),
+        Err(_) if is_intermediate_result => (format!("{part}: ✖ failed"), None),
+        Err(_) => (format!("{part}: ✖ failed      "), None),
+    };
+
+    if is_intermediate_result {
+        write_text(&str);
+    } else if let Some(result) = multiline_result {
+        write_text(&format!("\r{str}\n{result}\n"));
+    } else {
+        write_text(&format!("\r{str}\n"));
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured for the built-in client, or aoc-cli is installed.
+///  3. the answer is not known to be wrong from an earlier submission.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    id: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Result<Option<Verdict>, crate::template::Error> {
     let args: Vec<String> = env::args().collect();
 
-    if !args.contains(&"--submit".into()) {
-        return None;
+    let Some(index) = args.iter().position(|x| x == "--submit") else {
+        return Ok(None);
+    };
+
+    let Some(Ok(part_submit)) = args.get(index + 1).map(|x| x.parse::<u8>()) else {
+        return Err(crate::template::Error::Usage(
+            "unexpected command-line input. Format: cargo solve 1 --submit 1".into(),
+        ));
+    };
+
+    if part_submit != part {
+        return Ok(None);
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let client = aoc_client::native();
+
+    if client.is_none() && aoc_cli::check().is_err() {
+        return Err(crate::template::Error::MissingClient);
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    let answer = result.to_string();
+    let mut submissions = Submissions::read_from_file(id.year);
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    match submissions.check(id.day, part, &answer) {
+        Check::Refuse(reason) => {
+            eprintln!("Not submitting: {reason}");
+            return Ok(None);
+        }
+        Check::OutOfBounds(reason) => {
+            eprintln!("Warning: {reason}");
+        }
+        Check::Submit => {}
+    }
+
+    // NOTE: stdout is reserved for the answers, which may be JSON records.
+    let response = if let Some(client) = client {
+        eprintln!("Submitting result...");
+        let message = aoc_client::submit(&client, id, part, &answer)?;
+        eprintln!("{message}");
+        message
+    } else {
+        eprintln!("Submitting result via aoc-cli...");
+        let output = aoc_cli::submit(id, part, &answer)?;
+        String::from_utf8_lossy(&output.stdout).to_string()
     };
 
-    if part_submit != part {
-        return None;
+    let verdict = Verdict::parse(&response);
+    submissions.record(id.day, part, &answer, verdict);
+
+    if let Err(e) = submissions.store_file(id.year) {
+        eprintln!("Failed to log submission: {e}");
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    if verdict == Verdict::Correct {
+        let mut answers = Answers::read_from_file(id.year);
+        if answers.get(id.day, part).is_none() {
+            answers.set(id.day, part, answer);
+            if let Err(e) = answers.store_file(id.year) {
+                eprintln!("Failed to store accepted answer: {e}");
+            }
+        }
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Ok(Some(verdict))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::fmt::Display;
+
+    use super::Answer;
+
+    #[derive(Debug)]
+    struct Outer(std::num::ParseIntError);
+
+    impl Display for Outer {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("could not parse register")
+        }
+    }
+
+    impl std::error::Error for Outer {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn converts_options() {
+        assert_eq!(Some(42).to_answer(), Ok(Some("42".to_string())));
+        assert_eq!(None::<u32>.to_answer(), Ok(None));
+    }
+
+    #[test]
+    fn collects_error_chains() {
+        let ok: Result<u32, Outer> = Ok(7);
+        assert_eq!(ok.to_answer(), Ok(Some("7".to_string())));
+
+        let err: Result<u32, Outer> = "x".parse::<u32>().map_err(Outer);
+        assert_eq!(
+            err.to_answer(),
+            Err(vec![
+                "could not parse register".to_string(),
+                "invalid digit found in string".to_string()
+            ])
+        );
+    }
 }
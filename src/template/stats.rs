@@ -0,0 +1,139 @@
+/// Summary statistics for benchmark samples.
+use std::time::Duration;
+
+/// z-score used for the 95% confidence interval of the mean.
+const Z_95: f64 = 1.96;
+
+/// Multiplier of the inter-quartile range used for Tukey's outlier fences.
+const TUKEY_FENCE: f64 = 1.5;
+
+/// Statistics computed over the samples of a single benchmark run.
+/// All durations are stored as nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchStats {
+    pub samples: u128,
+    pub warmup: u128,
+    pub min: f64,
+    pub median: f64,
+    pub p95: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+    /// Half-width of the 95% confidence interval of the mean.
+    pub ci_95: f64,
+    /// Number of samples outside of Tukey's fences (1.5 × IQR).
+    pub outliers: u128,
+}
+
+impl BenchStats {
+    /// Computes statistics from a set of measured samples, excluding warm-up iterations.
+    /// Returns `None` if no samples were provided.
+    pub fn from_samples(samples: &[Duration], warmup: u128) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let n = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / n;
+
+        let variance = if nanos.len() > 1 {
+            nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+        let std_dev = variance.sqrt();
+
+        let q1 = percentile(&nanos, 0.25);
+        let q3 = percentile(&nanos, 0.75);
+        let iqr = q3 - q1;
+        let (fence_low, fence_high) = (q1 - TUKEY_FENCE * iqr, q3 + TUKEY_FENCE * iqr);
+
+        let outliers = nanos
+            .iter()
+            .filter(|&&x| x < fence_low || x > fence_high)
+            .count();
+
+        Some(Self {
+            samples: nanos.len() as u128,
+            warmup,
+            min: nanos[0],
+            median: percentile(&nanos, 0.5),
+            p95: percentile(&nanos, 0.95),
+            mean,
+            std_dev,
+            ci_95: Z_95 * std_dev / n.sqrt(),
+            outliers: outliers as u128,
+        })
+    }
+
+    /// The duration that represents the run, i.e. the median which is robust against outliers.
+    pub fn headline(&self) -> Duration {
+        nanos_to_duration(self.median)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn nanos_to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.max(0.0).round() as u64)
+}
+
+/// Linearly interpolated percentile of an ascending sorted, non-empty slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let weight = rank - lower as f64;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * weight
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BenchStats;
+    use std::time::Duration;
+
+    fn durations(nanos: &[u64]) -> Vec<Duration> {
+        nanos.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(BenchStats::from_samples(&[], 0), None);
+    }
+
+    #[test]
+    fn computes_basic_stats() {
+        let stats = BenchStats::from_samples(&durations(&[50, 10, 40, 20, 30]), 2).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.warmup, 2);
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.median, 30.0);
+        assert_eq!(stats.mean, 30.0);
+        assert_eq!(stats.p95, 48.0);
+        assert!((stats.std_dev - 250_f64.sqrt()).abs() < 1e-9);
+        assert!((stats.ci_95 - 1.96 * 250_f64.sqrt() / 5_f64.sqrt()).abs() < 1e-9);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn detects_outliers() {
+        let stats =
+            BenchStats::from_samples(&durations(&[10, 11, 10, 12, 11, 10, 500]), 0).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.median, 11.0);
+        assert_eq!(stats.headline(), Duration::from_nanos(11));
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = BenchStats::from_samples(&durations(&[42]), 0).unwrap();
+        assert_eq!(stats.min, 42.0);
+        assert_eq!(stats.median, 42.0);
+        assert_eq!(stats.p95, 42.0);
+        assert_eq!(stats.std_dev, 0.0);
+        assert_eq!(stats.outliers, 0);
+    }
+}
@@ -0,0 +1,395 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{get_year_path, Day, Year};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+
+/// The response of the Advent of Code server to a submission, as reported by aoc-cli.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint about the direction.
+    Incorrect,
+    /// Submitted too recently, the answer was not checked.
+    Wait,
+    Unknown,
+}
+
+impl Verdict {
+    /// Parses the verdict from the text printed by aoc-cli.
+    pub fn parse(output: &str) -> Self {
+        let output = output.to_lowercase();
+
+        if output.contains("that's the right answer") {
+            Verdict::Correct
+        } else if output.contains("too high") {
+            Verdict::TooHigh
+        } else if output.contains("too low") {
+            Verdict::TooLow
+        } else if output.contains("not the right answer") {
+            Verdict::Incorrect
+        } else if output.contains("answer too recently") || output.contains("left to wait") {
+            Verdict::Wait
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// Whether the server rejected the answer.
+    pub fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+        )
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Incorrect => "incorrect",
+            Verdict::Wait => "wait",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "wait" => Ok(Verdict::Wait),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// A single answer submitted to Advent of Code.
+#[derive(Clone, Debug)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// What to do with an answer before submitting it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    Submit,
+    /// Submit, but the answer lies outside of the bounds established by earlier submissions.
+    OutOfBounds(String),
+    /// Do not submit, the answer was submitted before.
+    Refuse(String),
+}
+
+/// Log of all submitted answers.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let path = get_year_path(year);
+        fs::create_dir_all(&path)?;
+        let mut file = fs::File::create(path.join(SUBMISSIONS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns an empty log.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(get_year_path(year).join(SUBMISSIONS_FILE_NAME))
+            .map_err(|x| x.to_string())
+            .and_then(Submissions::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Append a submission to the log.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, verdict: Verdict) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        self.data.push(Submission {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            timestamp,
+        });
+    }
+
+    /// Checks an answer against earlier submissions for the same day and part.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Check {
+        let previous: Vec<&Submission> = self
+            .data
+            .iter()
+            .filter(|s| s.day == day && s.part == part)
+            .collect();
+
+        if previous.iter().any(|s| s.verdict == Verdict::Correct) {
+            return Check::Refuse("this part was already solved.".into());
+        }
+
+        if let Some(s) = previous
+            .iter()
+            .find(|s| s.answer == answer && s.verdict.is_wrong())
+        {
+            return Check::Refuse(format!(
+                "{answer} was already submitted and is known to be wrong ({}).",
+                s.verdict.as_str().replace('_', " ")
+            ));
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Check::Submit;
+        };
+
+        let bound = |verdict: Verdict| {
+            previous
+                .iter()
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        if let Some(upper) = bound(Verdict::TooHigh).min().filter(|x| value >= *x) {
+            return Check::OutOfBounds(format!(
+                "{answer} is not lower than {upper}, which was too high."
+            ));
+        }
+
+        if let Some(lower) = bound(Verdict::TooLow).max().filter(|x| value <= *x) {
+            return Check::OutOfBounds(format!(
+                "{answer} is not higher than {lower}, which was too low."
+            ));
+        }
+
+        Check::Submit
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|t| *t as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            verdict,
+            timestamp,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Check, Submissions, Verdict};
+    use crate::day;
+
+    fn get_mock_submissions() -> Submissions {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(1), 1, "100", Verdict::TooHigh);
+        submissions.record(day!(1), 1, "20", Verdict::TooLow);
+        submissions.record(day!(1), 1, "50", Verdict::Incorrect);
+        submissions.record(day!(2), 1, "7", Verdict::Correct);
+        submissions.record(day!(3), 1, "abc", Verdict::Wait);
+        submissions
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::parse("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::parse("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::parse("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::parse("That's not the right answer. If you're stuck, ..."),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            Verdict::parse("You gave an answer too recently. You have 38s left to wait."),
+            Verdict::Wait
+        );
+        assert_eq!(Verdict::parse(""), Verdict::Unknown);
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let submissions = get_mock_submissions();
+        assert!(matches!(
+            submissions.check(day!(1), 1, "50"),
+            Check::Refuse(_)
+        ));
+        assert!(matches!(
+            submissions.check(day!(1), 1, "100"),
+            Check::Refuse(_)
+        ));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let submissions = get_mock_submissions();
+        assert!(matches!(
+            submissions.check(day!(2), 1, "8"),
+            Check::Refuse(_)
+        ));
+    }
+
+    #[test]
+    fn warns_about_answers_out_of_bounds() {
+        let submissions = get_mock_submissions();
+        assert!(matches!(
+            submissions.check(day!(1), 1, "120"),
+            Check::OutOfBounds(_)
+        ));
+        assert!(matches!(
+            submissions.check(day!(1), 1, "10"),
+            Check::OutOfBounds(_)
+        ));
+        assert_eq!(submissions.check(day!(1), 1, "42"), Check::Submit);
+    }
+
+    #[test]
+    fn allows_resubmitting_after_wait() {
+        let submissions = get_mock_submissions();
+        assert_eq!(submissions.check(day!(3), 1, "abc"), Check::Submit);
+        assert_eq!(submissions.check(day!(1), 2, "100"), Check::Submit);
+    }
+
+    #[test]
+    fn serializes_submissions() {
+        let submissions = get_mock_submissions();
+        let json = tinyjson::JsonValue::from(submissions).stringify().unwrap();
+        let parsed = Submissions::try_from(json).unwrap();
+        assert_eq!(parsed.data.len(), 5);
+        assert_eq!(parsed.data[0].verdict, Verdict::TooHigh);
+        assert_eq!(parsed.data[3].answer, "7");
+    }
+}
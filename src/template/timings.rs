@@ -1,9 +1,12 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::io::Error;
+use std::{collections::HashMap, fs, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::allocations::AllocStats;
+use crate::template::stats::BenchStats;
+use crate::template::{get_year_path, Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+pub static TIMINGS_FILE_NAME: &str = "timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -11,6 +14,10 @@ pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_stats: Option<BenchStats>,
+    pub part_2_stats: Option<BenchStats>,
+    pub part_1_allocations: Option<AllocStats>,
+    pub part_2_allocations: Option<AllocStats>,
     pub total_nanos: f64,
 }
 
@@ -23,15 +30,17 @@ pub struct Timings {
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let path = get_year_path(year);
+        fs::create_dir_all(&path)?;
+        let mut file = fs::File::create(path.join(TIMINGS_FILE_NAME))?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(get_year_path(year).join(TIMINGS_FILE_NAME))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
@@ -51,7 +60,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -65,6 +74,122 @@ impl Timings {
             .iter()
             .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
     }
+
+    /// Compare `new` timings against `self`, using the median of each part.
+    /// Timings stored without stats are compared by their formatted duration.
+    /// A part counts as regressed / improved if its relative change exceeds `tolerance` (e.g. `0.1` for 10%).
+    pub fn compare(&self, new: &Self, tolerance: f64) -> Vec<Comparison> {
+        let mut comparisons = vec![];
+
+        for timing in &new.data {
+            let stored = self.data.iter().find(|t| t.day == timing.day);
+
+            for part in [1, 2] {
+                let Some(current) = timing.part_nanos(part) else {
+                    continue;
+                };
+
+                let stored = stored.and_then(|t| t.part_nanos(part));
+
+                comparisons.push(Comparison {
+                    day: timing.day,
+                    part,
+                    stored,
+                    current,
+                    verdict: Verdict::new(stored, current, tolerance),
+                });
+            }
+        }
+
+        comparisons
+    }
+}
+
+impl Timing {
+    pub fn part_stats(&self, part: u8) -> Option<&BenchStats> {
+        match part {
+            1 => self.part_1_stats.as_ref(),
+            2 => self.part_2_stats.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Returns the median of a part in nanoseconds, reading the formatted duration if no stats are stored.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        if let Some(stats) = self.part_stats(part) {
+            return Some(stats.median);
+        }
+
+        let duration = match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }?;
+
+        parse_duration(duration)
+    }
+
+    pub fn part_allocations(&self, part: u8) -> Option<&AllocStats> {
+        match part {
+            1 => self.part_1_allocations.as_ref(),
+            2 => self.part_2_allocations.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Parses a duration formatted with `{:.1?}`, e.g. `48.2µs`, to nanoseconds.
+fn parse_duration(s: &str) -> Option<f64> {
+    let index = s.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (value, unit) = s.split_at(index);
+    let factor = match unit {
+        "ns" => 1.0,
+        "µs" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+    value.parse::<f64>().ok().map(|value| value * factor)
+}
+
+/// Outcome of comparing a part's timing against a stored baseline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Regressed,
+    Improved,
+    Unchanged,
+    /// No stored timing to compare against.
+    New,
+}
+
+impl Verdict {
+    fn new(stored: Option<f64>, current: f64, tolerance: f64) -> Self {
+        match stored {
+            None => Verdict::New,
+            Some(stored) if current > stored * (1.0 + tolerance) => Verdict::Regressed,
+            Some(stored) if current < stored * (1.0 - tolerance) => Verdict::Improved,
+            Some(_) => Verdict::Unchanged,
+        }
+    }
+}
+
+/// Comparison of a single day and part against stored timings. Durations are in nanoseconds.
+#[derive(Clone, Debug)]
+pub struct Comparison {
+    pub day: Day,
+    pub part: u8,
+    pub stored: Option<f64>,
+    pub current: f64,
+    pub verdict: Verdict,
+}
+
+impl Comparison {
+    /// Relative change against the stored timing, e.g. `0.25` for 25% slower.
+    pub fn change(&self) -> Option<f64> {
+        self.stored
+            .filter(|stored| *stored > 0.0)
+            .map(|stored| self.current / stored - 1.0)
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -133,6 +258,38 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "part_1_stats".into(),
+            value
+                .part_1_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_2_stats".into(),
+            value
+                .part_2_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_1_allocations".into(),
+            value
+                .part_1_allocations
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_2_allocations".into(),
+            value
+                .part_2_allocations
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -166,15 +323,140 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: stats are optional to stay compatible with timings stored by earlier versions.
+        let part_1_stats = parse_optional_stats(json.get("part_1_stats"))
+            .ok_or("Expected timing.part_1_stats to be null or a stats object.")?;
+
+        let part_2_stats = parse_optional_stats(json.get("part_2_stats"))
+            .ok_or("Expected timing.part_2_stats to be null or a stats object.")?;
+
+        let part_1_allocations = parse_optional_allocations(json.get("part_1_allocations"))
+            .ok_or("Expected timing.part_1_allocations to be null or an allocations object.")?;
+
+        let part_2_allocations = parse_optional_allocations(json.get("part_2_allocations"))
+            .ok_or("Expected timing.part_2_allocations to be null or an allocations object.")?;
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_stats,
+            part_2_stats,
+            part_1_allocations,
+            part_2_allocations,
             total_nanos,
         })
     }
 }
 
+fn parse_optional_stats(value: Option<&JsonValue>) -> Option<Option<BenchStats>> {
+    match value {
+        None => Some(None),
+        Some(v) if v.is_null() => Some(None),
+        Some(v) => BenchStats::try_from(v).ok().map(Some),
+    }
+}
+
+fn parse_optional_allocations(value: Option<&JsonValue>) -> Option<Option<AllocStats>> {
+    match value {
+        None => Some(None),
+        Some(v) if v.is_null() => Some(None),
+        Some(v) => AllocStats::try_from(v).ok().map(Some),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("warmup".into(), JsonValue::Number(value.warmup as f64));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("std_dev".into(), JsonValue::Number(value.std_dev));
+        map.insert("ci_95".into(), JsonValue::Number(value.ci_95));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(BenchStats {
+            samples: number("samples")? as u128,
+            warmup: number("warmup")? as u128,
+            min: number("min")?,
+            median: number("median")?,
+            p95: number("p95")?,
+            mean: number("mean")?,
+            std_dev: number("std_dev")?,
+            ci_95: number("ci_95")?,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&AllocStats> for JsonValue {
+    fn from(value: &AllocStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+        map.insert("bytes".into(), JsonValue::Number(value.bytes as f64));
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for AllocStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected allocations to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected allocations.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(AllocStats {
+            allocations: number("allocations")? as u64,
+            bytes: number("bytes")? as u64,
+            peak_bytes: number("peak_bytes")? as u64,
+        })
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
@@ -190,18 +472,30 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
                     total_nanos: 4e+10,
                 },
             ],
@@ -221,6 +515,38 @@ mod tests {
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.part_1_stats, None);
+        }
+
+        #[test]
+        fn handles_json_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "samples": 100, "warmup": 10, "min": 900000, "median": 1000000, "p95": 1200000, "mean": 1010000, "std_dev": 5000, "ci_95": 980, "outliers": 2 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.samples, 100);
+            assert_eq!(stats.median, 1_000_000_f64);
+            assert_eq!(stats.outliers, 2);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
+        #[test]
+        fn handles_json_allocations() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3000000, "part_1_allocations": { "allocations": 12, "bytes": 4096, "peak_bytes": 2048 }, "part_2_allocations": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let allocations = timing.part_1_allocations.unwrap();
+            assert_eq!(allocations.allocations, 12);
+            assert_eq!(allocations.bytes, 4096);
+            assert_eq!(allocations.peak_bytes, 2048);
+            assert_eq!(timing.part_2_allocations, None);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_malformed_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "samples": 100 } }] }"#.to_string();
+            Timings::try_from(json).unwrap();
         }
 
         #[test]
@@ -240,7 +566,7 @@ mod tests {
         #[test]
         #[should_panic]
         fn panics_for_malformed_timings() {
-            let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            let json = r#"{ "data": [{ "day": "01" }, { "day": "32" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
             Timings::try_from(json).unwrap();
         }
     }
@@ -281,11 +607,15 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -295,11 +625,15 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -309,11 +643,119 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod compare {
+        use crate::{
+            day,
+            template::{
+                stats::BenchStats,
+                timings::{parse_duration, Timing, Timings, Verdict},
+            },
+        };
+
+        fn stats(median: f64) -> BenchStats {
+            BenchStats {
+                samples: 10,
+                warmup: 1,
+                min: median,
+                median,
+                p95: median,
+                mean: median,
+                std_dev: 0.0,
+                ci_95: 0.0,
+                outliers: 0,
+            }
+        }
+
+        fn timing(day: u8, part_1: Option<f64>, part_2: Option<f64>) -> Timing {
+            Timing {
+                day: crate::template::Day::new(day).unwrap(),
+                part_1: part_1.map(|_| "-".into()),
+                part_2: part_2.map(|_| "-".into()),
+                part_1_stats: part_1.map(stats),
+                part_2_stats: part_2.map(stats),
+                part_1_allocations: None,
+                part_2_allocations: None,
+                total_nanos: part_1.unwrap_or(0.0) + part_2.unwrap_or(0.0),
+            }
+        }
+
+        #[test]
+        fn flags_regressions_and_improvements() {
+            let stored = Timings {
+                data: vec![
+                    timing(1, Some(100.0), Some(100.0)),
+                    timing(2, Some(100.0), None),
+                ],
+            };
+            let new = Timings {
+                data: vec![
+                    timing(1, Some(120.0), Some(80.0)),
+                    timing(2, Some(105.0), None),
+                ],
+            };
+
+            let comparisons = stored.compare(&new, 0.1);
+            assert_eq!(comparisons.len(), 3);
+            assert_eq!(comparisons[0].day, day!(1));
+            assert_eq!(comparisons[0].verdict, Verdict::Regressed);
+            assert_eq!(comparisons[1].verdict, Verdict::Improved);
+            assert_eq!(comparisons[2].verdict, Verdict::Unchanged);
+            assert!((comparisons[0].change().unwrap() - 0.2).abs() < 1e-9);
+        }
+
+        #[test]
+        fn handles_missing_baseline() {
+            let stored = Timings {
+                data: vec![timing(1, Some(100.0), None)],
+            };
+            let new = Timings {
+                data: vec![
+                    timing(1, Some(100.0), Some(50.0)),
+                    timing(3, Some(1.0), None),
+                ],
+            };
+
+            let comparisons = stored.compare(&new, 0.1);
+            assert_eq!(comparisons[0].verdict, Verdict::Unchanged);
+            assert_eq!(comparisons[1].verdict, Verdict::New);
+            assert_eq!(comparisons[2].verdict, Verdict::New);
+            assert_eq!(comparisons[2].change(), None);
+        }
+
+        #[test]
+        fn falls_back_to_stored_durations() {
+            let mut legacy = timing(1, None, None);
+            legacy.part_1 = Some("100.0ns".into());
+            legacy.part_2 = Some("1.0µs".into());
+            let stored = Timings { data: vec![legacy] };
+            let new = Timings {
+                data: vec![timing(1, Some(150.0), Some(1000.0))],
+            };
+
+            let comparisons = stored.compare(&new, 0.1);
+            assert_eq!(comparisons[0].stored, Some(100.0));
+            assert_eq!(comparisons[0].verdict, Verdict::Regressed);
+            assert_eq!(comparisons[1].verdict, Verdict::Unchanged);
+        }
+
+        #[test]
+        fn parses_durations() {
+            assert_eq!(parse_duration("48.2µs"), Some(48_200.0));
+            assert_eq!(parse_duration("1.5ms"), Some(1_500_000.0));
+            assert_eq!(parse_duration("2.0s"), Some(2e9));
+            assert_eq!(parse_duration("-"), None);
         }
     }
 
@@ -333,6 +775,10 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -353,6 +799,10 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
                     total_nanos: 0_f64,
                 }],
             };
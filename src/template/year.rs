@@ -0,0 +1,214 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::{Day, MAX_DAY};
+
+/// The first year Advent of Code took place.
+const FIRST_YEAR: u16 = 2015;
+
+/// The first year with a shorter event of 12 days.
+const FIRST_SHORT_YEAR: u16 = 2025;
+
+/// A valid year of Advent of Code (i.e. 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2024).unwrap();
+/// assert_eq!(year.to_string(), "2024")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a valid year of advent,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(FIRST_YEAR..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Reads the year from the name of a solution file, e.g. `src/bin/2024_01.rs`.
+    /// Fails to compile when used in a const context with a path that does not follow this scheme.
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __from_bin_path(path: &str) -> Self {
+        let bytes = path.as_bytes();
+        let len = bytes.len();
+
+        // NOTE: the file name is expected to be `YYYY_DD.rs`.
+        assert!(
+            len >= 10 && bytes[len - 6] == b'_' && bytes[len - 3] == b'.',
+            "solution files must be named `YYYY_DD.rs`, e.g. `src/bin/2024_01.rs`"
+        );
+
+        let mut year = 0;
+        let mut i = len - 10;
+        while i < len - 6 {
+            assert!(
+                bytes[i].is_ascii_digit(),
+                "solution files must be named `YYYY_DD.rs`, e.g. `src/bin/2024_01.rs`"
+            );
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        Self(year)
+    }
+
+    /// Returns the number of days of the year's event.
+    /// Can be overridden with the `AOC_DAYS_<YEAR>` environment variable, e.g. `AOC_DAYS_2025=12`.
+    pub fn day_count(self) -> u8 {
+        env::var(format!("AOC_DAYS_{}", self.0))
+            .ok()
+            .and_then(|x| x.parse::<u8>().ok())
+            .filter(|x| (1..=MAX_DAY).contains(x))
+            .unwrap_or(if self.0 >= FIRST_SHORT_YEAR { 12 } else { 25 })
+    }
+
+    /// Whether the day is part of the year's event.
+    pub fn has_day(self, day: Day) -> bool {
+        day.into_inner() <= self.day_count()
+    }
+
+    /// Returns the year set in the `AOC_YEAR` environment variable, if any.
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of advent, starting at 2015")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Identifies a single puzzle, i.e. a day of a specific year.
+///
+/// # Display
+/// This value displays as the year and day, separated by an underscore. This matches the name of the solution file.
+///
+/// ```
+/// # use advent_of_code::template::{Day, PuzzleId, Year};
+/// let id = PuzzleId::new(Year::new(2024).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(id.to_string(), "2024_08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}_{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015 && $year <= 9999,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting a year of advent, starting at 2015"
+            ),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PuzzleId, Year};
+    use crate::{day, year};
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2024".parse::<Year>().unwrap(), year!(2024));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("24".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn reads_year_from_bin_path() {
+        assert_eq!(Year::__from_bin_path("src/bin/2023_05.rs"), year!(2023));
+        assert_eq!(
+            Year::__from_bin_path("/home/ferris/aoc/src/bin/2024_25.rs"),
+            year!(2024)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_flat_bin_paths() {
+        Year::__from_bin_path("src/bin/05.rs");
+    }
+
+    #[test]
+    fn returns_day_count() {
+        assert_eq!(year!(2015).day_count(), 25);
+        assert_eq!(year!(2024).day_count(), 25);
+        assert_eq!(year!(2025).day_count(), 12);
+        assert!(year!(2025).has_day(day!(12)));
+        assert!(!year!(2025).has_day(day!(13)));
+    }
+
+    #[test]
+    fn displays_puzzle_ids() {
+        assert_eq!(PuzzleId::new(year!(2015), day!(1)).to_string(), "2015_01");
+    }
+}